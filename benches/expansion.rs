@@ -0,0 +1,202 @@
+//! Measures the time taken to expand large items.
+//!
+//! Procedural macros can only run within the compiler, so each scenario is a
+//! generated crate that is checked by Cargo. The same crate is also checked
+//! with the expanded attributes written directly, and the difference is the
+//! overhead of this crate. An error is reported if it exceeds the budget of
+//! the scenario, which is a ratio of the baseline time. Each budget is just
+//! above the ratio measured for that scenario, and all can be replaced using
+//! the `ATTR_ALIAS_BENCH_BUDGET` environment variable.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use std::time::Instant;
+
+const ALIASES: &str = "\
+*default=cfg(*)
+*unix_only=unix
+*supported=any(unix, windows)
+*fast=inline;
+";
+
+const RUNS: usize = 3;
+
+struct Scenario {
+    name: &'static str,
+    source: fn(bool) -> String,
+    budget: f64,
+}
+
+fn marker(eval: bool, alias: &str, attr: &str) -> String {
+    if eval {
+        format!("#[attr_alias({})]", alias)
+    } else {
+        format!("#[{}]", attr)
+    }
+}
+
+fn large_impl(eval: bool) -> String {
+    let mut source = String::new();
+    source += "pub struct Value;\n";
+    if eval {
+        source += "#[attr_alias::eval]\n";
+    }
+    source += "impl Value {\n";
+    for i in 0..5_000 {
+        let marker = marker(eval, "unix_only", "cfg(unix)");
+        writeln!(
+            source,
+            "    {}\n    pub fn method_{}(&self) {{}}",
+            marker, i
+        )
+        .expect("error writing source");
+    }
+    source += "}\n";
+    source
+}
+
+fn nested_macros(eval: bool) -> String {
+    const DEPTH: usize = 32;
+
+    let mut source = String::new();
+    for i in 0..DEPTH {
+        writeln!(source, "pub mod level_{} {{", i)
+            .expect("error writing source");
+        if eval {
+            source += "attr_alias::eval_block! {\n";
+        }
+        for j in 0..20 {
+            let marker = marker(eval, "supported", "cfg(any(unix, windows))");
+            writeln!(source, "{}\npub fn function_{}() {{}}", marker, j)
+                .expect("error writing source");
+        }
+    }
+    for _ in 0..DEPTH {
+        source += if eval { "}\n}\n" } else { "}\n" };
+    }
+    source
+}
+
+fn many_attributes(eval: bool) -> String {
+    let mut source = String::new();
+    if eval {
+        source += "#[attr_alias::eval]\n";
+    }
+    source += "pub mod items {\n";
+    for i in 0..3_000 {
+        for (alias, attr) in [
+            ("unix_only", "cfg(unix)"),
+            ("supported", "cfg(any(unix, windows))"),
+            ("fast", "inline"),
+        ] {
+            source += &marker(eval, alias, attr);
+            source += "\n";
+        }
+        writeln!(source, "pub fn function_{}() {{}}", i)
+            .expect("error writing source");
+    }
+    source += "}\n";
+    source
+}
+
+fn check(dir: &Path, target_dir: &Path) -> io::Result<()> {
+    let status = Command::new(env!("CARGO"))
+        .args(["check", "--quiet"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target_dir)
+        .status()?;
+    assert!(status.success(), "failed checking {}", dir.display());
+    Ok(())
+}
+
+fn measure(
+    dir: &Path,
+    target_dir: &Path,
+    source: &str,
+) -> io::Result<Duration> {
+    let lib = dir.join("src/lib.rs");
+    let mut fastest = Duration::MAX;
+    for _ in 0..RUNS {
+        // Rewriting the file causes the crate to be checked again.
+        fs::write(&lib, source)?;
+        let start = Instant::now();
+        check(dir, target_dir)?;
+        fastest = fastest.min(start.elapsed());
+    }
+    Ok(fastest)
+}
+
+fn main() -> io::Result<()> {
+    let budget: Option<f64> = env::var("ATTR_ALIAS_BENCH_BUDGET")
+        .ok()
+        .map(|x| x.parse().expect("invalid budget"));
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("expansion");
+    let target_dir = root.join("target");
+
+    let scenarios = [
+        Scenario {
+            name: "large_impl",
+            source: large_impl,
+            budget: 2.5,
+        },
+        Scenario {
+            name: "nested_macros",
+            source: nested_macros,
+            budget: 2.5,
+        },
+        Scenario {
+            name: "many_attributes",
+            source: many_attributes,
+            budget: 3.5,
+        },
+    ];
+
+    let mut exceeded = Vec::new();
+    for scenario in &scenarios {
+        let dir = root.join(scenario.name);
+        fs::create_dir_all(dir.join("src"))?;
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.0.0\"\n\
+                 edition = \"2021\"\npublish = false\n\n\
+                 [dependencies]\nattr_alias = {{ path = {:?} }}\n\n\
+                 [workspace]\n",
+                scenario.name,
+                env!("CARGO_MANIFEST_DIR"),
+            ),
+        )?;
+        fs::write(dir.join("src/attr-aliases.txt"), ALIASES)?;
+
+        // Dependencies are built before measuring.
+        fs::write(dir.join("src/lib.rs"), "")?;
+        check(&dir, &target_dir)?;
+
+        let baseline = measure(&dir, &target_dir, &(scenario.source)(false))?;
+        let eval = measure(&dir, &target_dir, &(scenario.source)(true))?;
+        let ratio = eval.as_secs_f64() / baseline.as_secs_f64();
+        println!(
+            "{:<16} baseline {:>8.3}s    eval {:>8.3}s    ratio {:>5.2}",
+            scenario.name,
+            baseline.as_secs_f64(),
+            eval.as_secs_f64(),
+            ratio,
+        );
+        let budget = budget.unwrap_or(scenario.budget);
+        if ratio > budget {
+            exceeded.push(format!("{} ({:.2})", scenario.name, budget));
+        }
+    }
+
+    assert!(
+        exceeded.is_empty(),
+        "expansion exceeded the budget for: {}",
+        exceeded.join(", "),
+    );
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::env;
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split(['.', ' ']).nth(2)?.parse().ok()
+}
+
+// Returns whether the compiler accepts the source without enabling unstable
+// features.
+fn compiles(source: &str) -> bool {
+    let (Some(rustc), Some(out_dir)) =
+        (env::var_os("RUSTC"), env::var_os("OUT_DIR"))
+    else {
+        return false;
+    };
+    let Ok(mut process) = Command::new(rustc)
+        .args([
+            "--crate-name=attr_alias_probe",
+            "--crate-type=lib",
+            "--edition=2021",
+            "--emit=metadata",
+            "--out-dir",
+        ])
+        .arg(out_dir)
+        .arg("-")
+        .env_remove("RUSTC_BOOTSTRAP")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    let written = process
+        .stdin
+        .take()
+        .is_some_and(|mut x| x.write_all(source.as_bytes()).is_ok());
+    process.wait().is_ok_and(|x| x.success()) && written
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(attr_alias_span_file)");
+    println!("cargo:rustc-check-cfg=cfg(attr_alias_tracked_path)");
+
+    // [Span::local_file] and other location methods were stabilized in Rust
+    // 1.88.
+    if rustc_minor_version().is_some_and(|x| x >= 88) {
+        println!("cargo:rustc-cfg=attr_alias_span_file");
+    }
+
+    // [tracked::path] is unstable, but it will be used without the nightly
+    // feature once the compiler accepts it.
+    if env::var_os("CARGO_FEATURE_NIGHTLY").is_some()
+        || compiles(
+            "extern crate proc_macro;\n\
+             pub fn track() { proc_macro::tracked::path(\"\"); }\n",
+        )
+    {
+        println!("cargo:rustc-cfg=attr_alias_tracked_path");
+    }
+}
@@ -1,93 +1,1592 @@
-use std::collections::HashMap;
+#[cfg(feature = "testing")]
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::env;
+use std::fs;
 use std::fs::OpenOptions;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io;
 use std::io::Read;
+use std::iter;
+use std::path::Path;
+use std::path::PathBuf;
+use std::result;
+use std::slice;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::time::SystemTime;
 
+#[cfg(attr_alias_tracked_path)]
+use proc_macro::tracked;
 use proc_macro::Delimiter;
 use proc_macro::Group;
 use proc_macro::Ident;
+use proc_macro::Literal;
 use proc_macro::Punct;
 use proc_macro::Spacing;
 use proc_macro::Span;
 use proc_macro::TokenStream;
 use proc_macro::TokenTree;
 
+use super::cfg;
+use super::is_colon;
+use super::is_comma;
+use super::is_ident;
+use super::lexer;
+use super::names;
+use super::path;
+use super::syntax;
+use super::syntax::Chunk;
+use super::syntax::Directive;
+use super::syntax::Item;
+use super::token::Token;
+use super::usage;
+use super::warning;
+use super::Code;
 use super::Error;
 use super::Result;
 
-macro_rules! next {
-    ( $item:expr , $type:ident $(, $method:ident => $value:expr)? $(,)? ) => {
-        if let Some(token) = $item.next() {
-            match token {
-                TokenTree::$type(x) $(if x.$method() == $value)? => Ok(x),
-                _ => Err(Error::token(&token)),
+fn is_semicolon(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(x) if x.as_char() == ';')
+}
+
+fn is_bundle(value: &TokenStream) -> bool {
+    value.clone().into_iter().any(|x| is_semicolon(&x))
+}
+
+// Only the last attribute of a bundle can be empty, which allows a bundle to
+// contain a single attribute.
+fn has_empty_attr(value: &TokenStream) -> bool {
+    let tokens: Vec<_> = value.clone().into_iter().collect();
+    let mut attrs = tokens.split(is_semicolon);
+    let _ = attrs.next_back();
+    attrs.any(<[_]>::is_empty)
+}
+
+fn split_bundle(value: TokenStream) -> Vec<TokenStream> {
+    let mut value = value.into_iter().peekable();
+    let mut attrs = Vec::new();
+    while value.peek().is_some() {
+        let attr: TokenStream =
+            value.by_ref().take_while(|x| !is_semicolon(x)).collect();
+        if !attr.is_empty() {
+            attrs.push(attr);
+        }
+    }
+    attrs
+}
+
+// Values are tokenized once, when they are first used.
+struct Value {
+    text: String,
+    tokens: Vec<Token>,
+}
+
+// Entries are indexed when the alias file is parsed, but their values are
+// only resolved when they are first used. Crates using few of the aliases in
+// a large file then do not pay for the rest.
+struct Entry {
+    index: usize,
+    line: usize,
+    // Offsets of the value within the alias file.
+    value_start: usize,
+    value_end: usize,
+    value: OnceLock<Value>,
+}
+
+// Markers are usually repeated with the same arguments, so their
+// resolutions are reused.
+struct Resolution {
+    names: Vec<String>,
+    default: bool,
+    pattern: String,
+    attrs: Vec<Vec<Token>>,
+}
+
+// Decoration of the trigger, given by the "trigger-attr" and
+// "trigger-prefix" directives.
+#[cfg_attr(attr_alias_tracked_path, allow(dead_code))]
+#[derive(Default)]
+struct Trigger {
+    attrs: Vec<Vec<Token>>,
+    prefix: Option<String>,
+}
+
+struct File {
+    source: String,
+    entries: BTreeMap<String, Entry>,
+    allow_lints: bool,
+    // Names declared by build scripts cannot be read, so unexpected names are
+    // only reported when requested by the "check-cfg-names" directive.
+    check_cfg_names: bool,
+    // The trigger is not emitted when paths can be tracked.
+    #[cfg_attr(attr_alias_tracked_path, allow(dead_code))]
+    trigger: Trigger,
+}
+
+fn count_wildcards(pattern: &TokenStream) -> usize {
+    pattern
+        .clone()
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => count_wildcards(&group.stream()),
+            TokenTree::Punct(x) => (x.as_char() == '*').into(),
+            _ => 0,
+        })
+        .sum()
+}
+
+// Patterns without wildcards usually have an empty group where the value
+// should be substituted (e.g., "cfg()") or omit the group entirely.
+fn suggest_wildcard(pattern: &TokenStream) -> Option<TokenStream> {
+    fn fill(tokens: &mut [TokenTree]) -> bool {
+        for token in tokens {
+            let TokenTree::Group(group) = token else {
+                continue;
+            };
+            let mut stream: Vec<_> = group.stream().into_iter().collect();
+            if group.delimiter() == Delimiter::Parenthesis && stream.is_empty()
+            {
+                stream.push(Punct::new('*', Spacing::Alone).into());
+            } else if !fill(&mut stream) {
+                continue;
+            }
+            let mut new_group =
+                Group::new(group.delimiter(), stream.into_iter().collect());
+            new_group.set_span(group.span());
+            *token = new_group.into();
+            return true;
+        }
+        false
+    }
+
+    let mut tokens: Vec<_> = pattern.clone().into_iter().collect();
+    if !fill(&mut tokens) {
+        if !matches!(tokens.last(), Some(TokenTree::Ident(_))) {
+            return None;
+        }
+        tokens.push(
+            Group::new(
+                Delimiter::Parenthesis,
+                tokens!(Punct::new('*', Spacing::Alone),).collect(),
+            )
+            .into(),
+        );
+    }
+    Some(tokens.into_iter().collect())
+}
+
+fn pluralize(count: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+// Wildcards are replaced in order by the values, which must include one for
+// each wildcard.
+fn substitute(
+    pattern: TokenStream,
+    values: &[TokenStream],
+    index: &mut usize,
+) -> TokenStream {
+    pattern
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Punct(x) if x.as_char() == '*' => {
+                *index += 1;
+                values[*index - 1].clone()
             }
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    substitute(group.stream(), values, index),
+                );
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group).into()
+            }
+            _ => token.into(),
+        })
+        .collect()
+}
+
+// Names given to markers are negated when written as "!name" or "not(name)".
+fn parse_name(arg: &[TokenTree]) -> Option<(Ident, bool)> {
+    match arg {
+        [TokenTree::Ident(name)] => Some((name.clone(), false)),
+        [TokenTree::Punct(x), TokenTree::Ident(name)]
+            if x.as_char() == '!' =>
+        {
+            Some((name.clone(), true))
+        }
+        [TokenTree::Ident(x), TokenTree::Group(group)]
+            if x.to_string() == "not"
+                && group.delimiter() == Delimiter::Parenthesis =>
+        {
+            match &group.stream().into_iter().collect::<Vec<_>>()[..] {
+                [TokenTree::Ident(name)] => Some((name.clone(), true)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_keyword<'a>(arg: &'a [TokenTree], name: &str) -> Option<&'a Ident> {
+    let [arg_name, punct, TokenTree::Ident(value)] = arg else {
+        return None;
+    };
+    (matches!(arg_name, TokenTree::Ident(x) if x.to_string() == name)
+        && matches!(punct, TokenTree::Punct(x) if x.as_char() == '='))
+    .then_some(value)
+}
+
+fn parse_unsafe(attr: &TokenStream) -> Option<(Ident, Group)> {
+    let mut attr = attr.clone().into_iter();
+    let keyword = next!(attr, Ident, to_string => "unsafe").ok()?;
+    let group =
+        next!(attr, Group, delimiter => Delimiter::Parenthesis).ok()?;
+    attr.next().is_none().then_some((keyword, group))
+}
+
+// Names given to markers are replaced with the aliases to which they are
+// bound. Names within patterns are kept, since they may not be aliases.
+pub(super) fn bind_names<F>(attr: TokenStream, bound: &F) -> TokenStream
+where
+    F: Fn(&Ident) -> Option<Ident>,
+{
+    fn bind<F>(token: &mut TokenTree, bound: &F)
+    where
+        F: Fn(&Ident) -> Option<Ident>,
+    {
+        match token {
+            TokenTree::Ident(name) => {
+                if let Some(mut alias) = bound(name) {
+                    alias.set_span(name.span());
+                    *name = alias;
+                }
+            }
+            // Negations are written as "not(name)".
+            TokenTree::Group(group) => {
+                let mut tokens: Vec<_> = group.stream().into_iter().collect();
+                tokens.iter_mut().for_each(|x| bind(x, bound));
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    tokens.into_iter().collect(),
+                );
+                new_group.set_span(group.span());
+                *group = new_group;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((keyword, group)) = parse_unsafe(&attr) {
+        let mut new_group = Group::new(
+            Delimiter::Parenthesis,
+            bind_names(group.stream(), bound),
+        );
+        new_group.set_span(group.span());
+        return tokens!(keyword, new_group,).collect();
+    }
+
+    let mut tokens = attr.clone().into_iter();
+    let (Ok(marker), Ok(group), None) = (
+        next!(tokens, Ident, to_string => "attr_alias"),
+        next!(tokens, Group, delimiter => Delimiter::Parenthesis),
+        tokens.next(),
+    ) else {
+        return attr;
+    };
+    let mut args = group.stream().into_iter().peekable();
+    let mut new_args = TokenStream::new();
+    let mut names = true;
+    while args.peek().is_some() {
+        let mut arg = Vec::new();
+        while let Some(token) = args.next_if(|x| !is_comma(x)) {
+            arg.push(token);
+        }
+        if names && parse_keyword(&arg, "level").is_none() {
+            if parse_name(&arg).is_some() {
+                if let Some(token) = arg.last_mut() {
+                    bind(token, bound);
+                }
+            } else {
+                names = false;
+            }
+        }
+        new_args.extend(arg);
+        new_args.extend(args.next());
+    }
+    let mut new_group = Group::new(Delimiter::Parenthesis, new_args);
+    new_group.set_span(group.span());
+    tokens!(marker, new_group,).collect()
+}
+
+fn is_valid_attr(attr: &TokenStream) -> bool {
+    if let Some((_, group)) = parse_unsafe(attr) {
+        return is_valid_attr(&group.stream());
+    }
+
+    // Paths are separated by "::", which is also allowed at the start.
+    let mut attr = attr.clone().into_iter().peekable();
+    let mut separator = attr.next_if(is_colon).is_some();
+    loop {
+        if separator && attr.next_if(is_colon).is_none() {
+            return false;
+        }
+        if attr.next_if(|x| matches!(x, TokenTree::Ident(_))).is_none() {
+            return false;
+        }
+        separator = attr.next_if(is_colon).is_some();
+        if !separator {
+            break;
+        }
+    }
+
+    match attr.next() {
+        None => true,
+        Some(TokenTree::Group(_)) => attr.next().is_none(),
+        Some(TokenTree::Punct(x)) if x.as_char() == '=' => {
+            attr.peek().is_some() && !attr.any(|x| is_semicolon(&x))
+        }
+        _ => false,
+    }
+}
+
+fn parse_macro_call<'a>(
+    tokens: &'a [TokenTree],
+    names: &[&str],
+) -> Option<(&'a Ident, &'a Group)> {
+    let [operator, punct, args, ..] = tokens else {
+        return None;
+    };
+    match (operator, punct, args) {
+        (
+            TokenTree::Ident(operator),
+            TokenTree::Punct(punct),
+            TokenTree::Group(args),
+        ) if names.contains(&&*operator.to_string())
+            && punct.as_char() == '!'
+            && args.delimiter() == Delimiter::Parenthesis =>
+        {
+            Some((operator, args))
+        }
+        _ => None,
+    }
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(attr_alias_span_file)]
+pub(super) fn location(span: Span) -> String {
+    format!("{}:{}:{}", span.file(), span.line(), span.column())
+}
+
+#[cfg(not(attr_alias_span_file))]
+pub(super) fn location(span: Span) -> String {
+    format!("{:?}", span)
+}
+
+// Resolutions are printed when requested, to show what was expanded when it
+// cannot be seen otherwise.
+fn debug<F>(span: Span, description: F)
+where
+    F: FnOnce() -> String,
+{
+    if env::var_os("ATTR_ALIAS_DEBUG").is_some_and(|x| x == "1") {
+        eprintln!("attr_alias: {}: {}", location(span), description());
+    }
+}
+
+// Spans are only valid within the invocation that created them, so errors
+// are cached without them and reported at the span of each invocation.
+struct CachedError {
+    code: Code,
+    message: String,
+    notes: Vec<String>,
+}
+
+impl CachedError {
+    fn new(error: &Error) -> Self {
+        Self {
+            code: error.code,
+            message: error.message.clone(),
+            notes: error.notes.iter().map(|(_, x)| x.clone()).collect(),
+        }
+    }
+
+    fn to_error(&self) -> Error {
+        self.notes.iter().fold(
+            Error::spanned(self.code, Span::call_site(), self.message.clone()),
+            |error, note| error.note(None, note.clone()),
+        )
+    }
+}
+
+// The length and modification time of the file are compared before its
+// contents, since reading and hashing them for every invocation is slow.
+struct CachedSnapshot {
+    stamp: Option<(u64, SystemTime)>,
+    hash: u64,
+    snapshot: result::Result<Arc<Aliases>, CachedError>,
+}
+
+impl CachedSnapshot {
+    fn to_result(&self) -> Result<Arc<Aliases>> {
+        self.snapshot
+            .as_ref()
+            .map(Arc::clone)
+            .map_err(CachedError::to_error)
+    }
+}
+
+const DEFINITION_FORM: &str =
+    "aliases are defined as `*name = value`, with one alias per line";
+
+// Lines and columns are counted from 1, and columns count characters. They
+// are only needed for errors, so they are computed from offsets when those
+// occur.
+fn locate(source: &str, offset: usize) -> impl FnOnce(Error) -> Error {
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |x| x + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    move |error| Error {
+        code: match error.code {
+            Code::Syntax => Code::Parsing,
+            code => code,
+        },
+        span: error.span,
+        message: format!(
+            "{}:{}:{}: {}",
+            Aliases::FILE,
+            line,
+            column,
+            error.message,
+        ),
+        notes: error.notes,
+        end_span: error.end_span,
+    }
+}
+
+// Missing tokens are located where they were expected, which is on the same
+// line as the preceding ones.
+fn next_offset(source: &str, offset: usize, end: usize) -> usize {
+    let rest = &source[offset..end];
+    if rest.trim().is_empty() {
+        offset
+    } else {
+        end - rest.trim_start().len()
+    }
+}
+
+// Comments are skipped in the same way as by the compiler, including nested
+// block comments. An unterminated comment is left to be reported as invalid.
+fn skip_trivia(mut text: &str) -> &str {
+    loop {
+        text = text.trim_start();
+        if let Some(rest) = text.strip_prefix("//") {
+            text = rest.find('\n').map_or("", |x| &rest[x..]);
+        } else if text.starts_with("/*") {
+            let mut depth = 0_usize;
+            let mut rest = text;
+            loop {
+                if let Some(next) = rest.strip_prefix("/*") {
+                    depth += 1;
+                    rest = next;
+                } else if let Some(next) = rest.strip_prefix("*/") {
+                    depth -= 1;
+                    rest = next;
+                    if depth == 0 {
+                        break;
+                    }
+                } else if let Some(ch) = rest.chars().next() {
+                    rest = &rest[ch.len_utf8()..];
+                } else {
+                    return text;
+                }
+            }
+            text = rest;
         } else {
-            Err(Error::new("unexpected end of tokens"))
+            return text;
         }
     }
 }
 
-fn is_comma(token: &TokenTree) -> bool {
-    matches!(token, TokenTree::Punct(x) if x.as_char() == ',')
+// Names are identifiers, which can be raw.
+// The compiler aborts procedural macros for some invalid text, instead of
+// returning an error, so the text is tokenized before it is parsed.
+fn parse_text(source: &str, start: usize, end: usize) -> Result<TokenStream> {
+    if let Err(error) = lexer::tokenize(source, start, end) {
+        return Err(locate(source, error.offset)(Error::spanned(
+            Code::Parsing,
+            Span::call_site(),
+            format!("error parsing alias file: {}", error.message),
+        )));
+    }
+    source[start..end].parse().map_err(|x| {
+        locate(source, next_offset(source, start, end))(Error::new_from(
+            Code::Parsing,
+            x,
+            "parsing alias file",
+        ))
+    })
 }
 
-pub(super) struct Aliases(HashMap<String, String>);
+fn definition_error(span: Span, message: String) -> Error {
+    Error::spanned(Code::Parsing, span, message)
+        .note(None, DEFINITION_FORM.to_owned())
+}
+
+// Tables given to [assert_expansion!] replace the alias file while its
+// input is expanded.
+#[cfg(feature = "testing")]
+thread_local! {
+    static TABLE: RefCell<Option<Arc<Aliases>>> = const { RefCell::new(None) };
+}
+
+pub(super) struct Aliases {
+    file: Arc<File>,
+    // Configuration names can be restricted by the "allow-cfg" directive.
+    allowed_cfgs: Option<Vec<String>>,
+    // Values can only reference aliases defined before them.
+    visible: usize,
+    // Documentation attributes can be added by the "doc-cfg" directive, for
+    // the listed aliases or all of them if none are listed.
+    doc_cfgs: Option<Vec<String>>,
+    // Text from the "doc-text" directive replaces those attributes.
+    doc_texts: Arc<BTreeMap<String, String>>,
+    // References within the alias file are not uses by markers, and their
+    // expansions are not documented.
+    markers: bool,
+    resolutions: Mutex<BTreeMap<String, Resolution>>,
+}
 
 impl Aliases {
+    const DEFAULT_NAME: &'static str = "default";
+    const RAW_DEFAULT_NAME: &'static str = "r#default";
     pub(super) const FILE: &'static str = alias_file!();
+    // Lints configured for the crate should not be reported for items that
+    // it does not write.
+    const LINT_ATTR: &'static str = "#[allow(warnings, clippy::all, \
+                                      clippy::pedantic, clippy::nursery, \
+                                      clippy::restriction)]";
+
+    // The default alias can only be referenced using a raw identifier.
+    fn names(&self) -> impl Iterator<Item = &str> {
+        self.file
+            .entries
+            .iter()
+            .filter(|(_, x)| x.index < self.visible)
+            .map(|(x, _)| {
+                if x == Self::DEFAULT_NAME {
+                    Self::RAW_DEFAULT_NAME
+                } else {
+                    x
+                }
+            })
+    }
+
+    fn documents(&self, names: &[Ident]) -> bool {
+        self.markers
+            && self.doc_cfgs.as_ref().is_some_and(|doc_cfgs| {
+                doc_cfgs.is_empty()
+                    || names.iter().any(|x| doc_cfgs.contains(&x.to_string()))
+            })
+    }
+
+    fn record_usage(&self, name: &str, span: Span) {
+        #[cfg(feature = "testing")]
+        if TABLE.with(|x| x.borrow().is_some()) {
+            return;
+        }
+        if self.markers {
+            usage::record(self.names(), name, span);
+        }
+    }
+
+    fn check_cfgs(&self, names: Vec<Ident>) -> Result<()> {
+        let Some(allowed_cfgs) = &self.allowed_cfgs else {
+            return Ok(());
+        };
+        names
+            .into_iter()
+            .find(|x| !allowed_cfgs.contains(&x.to_string()))
+            .map_or(Ok(()), |name| {
+                Err(Error::spanned(
+                    Code::DisallowedCfg,
+                    name.span(),
+                    format!(
+                        "configuration name '{}' is not allowed by the alias \
+                         file",
+                        name,
+                    ),
+                )
+                .note(
+                    None,
+                    format!("allowed names: {}", allowed_cfgs.join(", ")),
+                ))
+            })
+    }
+
+    fn get_alias(&self, name: &Ident) -> Result<&Value> {
+        // The default alias does not usually make sense to nest, as the only
+        // way to nest it would be to nest [#[attr_alias]], which already has
+        // syntax for it to be implicitly used. However, it can be referenced
+        // explicitly using a raw identifier.
+        let name_string = name.to_string();
+        Some(name_string.as_str())
+            .filter(|&x| x != Self::DEFAULT_NAME)
+            .map(|x| {
+                if x == Self::RAW_DEFAULT_NAME {
+                    Self::DEFAULT_NAME
+                } else {
+                    x
+                }
+            })
+            .and_then(|x| self.entry(x))
+            .ok_or_else(|| {
+                let mut names: Vec<_> = self.names().collect();
+                names.sort_unstable();
+                Error::spanned(
+                    Code::UnknownAlias,
+                    name.span(),
+                    format!("unknown alias '{}'", name),
+                )
+                .note(
+                    None,
+                    if names.is_empty() {
+                        "no aliases are defined".to_owned()
+                    } else {
+                        format!("defined aliases: {}", names.join(", "))
+                    },
+                )
+            })
+            .and_then(|(name, entry)| self.resolve_entry(name, entry))
+    }
+
+    // Spans cannot refer to the alias file, so diagnostics give the locations
+    // of definitions in notes instead, which editors and terminals usually
+    // recognize as links.
+    fn definition(&self, name: &str) -> String {
+        let name = if name == Self::RAW_DEFAULT_NAME {
+            Self::DEFAULT_NAME
+        } else {
+            name
+        };
+        let Some(entry) = self.file.entries.get(name) else {
+            return Self::FILE.to_owned();
+        };
+        let column = self
+            .file
+            .source
+            .split('\n')
+            .nth(entry.line - 1)
+            .and_then(|line| {
+                line.find(name).map(|x| line[..x].chars().count() + 1)
+            })
+            .unwrap_or(1);
+        format!("{}:{}:{}", Self::FILE, entry.line, column)
+    }
+
+    fn entry(&self, name: &str) -> Option<(&str, &Entry)> {
+        self.file
+            .entries
+            .get_key_value(name)
+            .filter(|(_, x)| x.index < self.visible)
+            .map(|(name, entry)| (name.as_str(), entry))
+    }
+
+    fn resolve_entry<'a>(
+        &self,
+        name: &str,
+        entry: &'a Entry,
+    ) -> Result<&'a Value> {
+        if let Some(value) = entry.value.get() {
+            return Ok(value);
+        }
+
+        let source = &self.file.source;
+        let value_offset =
+            next_offset(source, entry.value_start, entry.value_end);
+        let locate = || locate(source, value_offset);
+        let value = parse_text(source, entry.value_start, entry.value_end)?;
+        if value.is_empty() {
+            return Err(locate()(definition_error(
+                Span::call_site(),
+                format!("alias '{}' has no value", name),
+            )));
+        }
+        if has_empty_attr(&value) {
+            return Err(locate()(definition_error(
+                Span::call_site(),
+                format!("alias '{}' contains an empty attribute", name),
+            )));
+        }
 
+        let aliases = Self {
+            file: Arc::clone(&self.file),
+            allowed_cfgs: self.allowed_cfgs.clone(),
+            doc_cfgs: self.doc_cfgs.clone(),
+            doc_texts: Arc::clone(&self.doc_texts),
+            visible: entry.index,
+            markers: false,
+            resolutions: Mutex::new(BTreeMap::new()),
+        };
+        // The default alias is a pattern, so references within it can be
+        // anywhere.
+        let (text, tokens) = if name == Self::DEFAULT_NAME {
+            aliases
+                .resolve_nested(value, Span::call_site())
+                .map(|x| (x.to_string(), Token::from_stream(x)))
+        } else {
+            aliases
+                .resolve(value.clone(), Span::call_site())
+                .map(|attrs| {
+                    let Some(attrs) = attrs else {
+                        return (value.to_string(), Token::from_stream(value));
+                    };
+                    let text: Vec<_> =
+                        attrs.iter().map(ToString::to_string).collect();
+                    let mut tokens = Vec::new();
+                    for (i, attr) in attrs.into_iter().enumerate() {
+                        if i != 0 {
+                            tokens.push(Token::Punct(';', Spacing::Alone));
+                        }
+                        tokens.extend(Token::from_stream(attr));
+                    }
+                    (text.join("; "), tokens)
+                })
+        }
+        .map_err(locate())?;
+        for attr in split_bundle(Token::to_stream(&tokens, Span::call_site()))
+        {
+            aliases
+                .check_cfgs(cfg::attr_names(&attr))
+                .map_err(locate())?;
+        }
+
+        // Another thread may have resolved the same value concurrently, but
+        // the results will be equal.
+        let _ = entry.value.set(Value { text, tokens });
+        Ok(entry.value.get().expect("value was not set"))
+    }
+
+    fn get_value(&self, name: &Ident, span: Span) -> Result<TokenStream> {
+        Ok(Token::to_stream(&self.get_alias(name)?.tokens, span))
+    }
+
+    // Uses are recorded and resolutions are printed for each marker, even
+    // when the resolution was reused.
+    fn finish_args(
+        &self,
+        resolution: &Resolution,
+        attrs: &[TokenStream],
+        span: Span,
+    ) {
+        for name in &resolution.names {
+            self.record_usage(name, span);
+        }
+        if resolution.default {
+            self.record_usage(Self::RAW_DEFAULT_NAME, span);
+        }
+
+        debug(span, || {
+            let attrs: Vec<_> =
+                attrs.iter().map(|x| format!("#[{}]", x)).collect();
+            let names: Vec<_> = resolution
+                .names
+                .iter()
+                .map(|x| format!("'{}' ({})", x, self.definition(x)))
+                .collect();
+            format!(
+                "resolved {} with pattern `{}` to `{}`",
+                names.join(", "),
+                resolution.pattern,
+                attrs.join(" "),
+            )
+        });
+    }
+
+    // Substituted tokens are given the span of the attribute, so diagnostics
+    // for the expansion will point to it. Reused resolutions are given that
+    // span entirely.
     pub(super) fn resolve_args(
         &self,
         args: TokenStream,
+        span: Span,
+    ) -> Result<Vec<TokenStream>> {
+        let key = args.to_string();
+        {
+            let resolutions = self
+                .resolutions
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if let Some(resolution) = resolutions.get(&key) {
+                let attrs: Vec<_> = resolution
+                    .attrs
+                    .iter()
+                    .map(|x| Token::to_stream(x, span))
+                    .collect();
+                self.finish_args(resolution, &attrs, span);
+                return Ok(attrs);
+            }
+        }
+
+        let mut warned = false;
+        let (resolution, attrs) =
+            self.resolve_new_args(args, span, &mut warned)?;
+        self.finish_args(&resolution, &attrs, span);
+        // Resolutions with warnings are not reused, so that each marker will
+        // be reported.
+        if !warned {
+            let _ = self
+                .resolutions
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(key, resolution);
+        }
+        Ok(attrs)
+    }
+
+    fn resolve_new_args(
+        &self,
+        args: TokenStream,
+        span: Span,
+        warned: &mut bool,
+    ) -> Result<(Resolution, Vec<TokenStream>)> {
+        const LINT_LEVELS: &[&str] =
+            &["allow", "deny", "expect", "forbid", "warn"];
+
+        let mut args = args.into_iter().peekable();
+        let mut arg = Vec::new();
+        while let Some(token) = args.next_if(|x| !is_comma(x)) {
+            arg.push(token);
+        }
+        let Some((name, negated)) = parse_name(&arg) else {
+            let mut arg = arg.into_iter().chain(args.next());
+            let _ = next!(arg, Ident)?;
+            return Err(Error::token(&arg.next().expect("missing token")));
+        };
+        let mut names = vec![name];
+        let mut negations = vec![negated];
+        let mut pattern: Option<TokenStream> = None;
+        let mut pattern_span = names[0].span();
+        let mut level = None;
+        while args.peek().is_some() {
+            let _ = next!(args, Punct, as_char => ',')?;
+            let mut arg = Vec::new();
+            while let Some(token) = args.next_if(|x| !is_comma(x)) {
+                arg.push(token);
+            }
+            if arg.is_empty() {
+                if let Some(token) = args.peek() {
+                    return Err(Error::token(token));
+                }
+            } else if let Some(value) = parse_keyword(&arg, "level") {
+                if !LINT_LEVELS.contains(&&*value.to_string()) {
+                    return Err(Error::spanned(
+                        Code::Level,
+                        value.span(),
+                        format!("invalid lint level '{}'", value),
+                    ));
+                }
+                level = Some(value.clone());
+            } else if pattern.is_some() {
+                return Err(Error::token(&arg[0]));
+            } else if let Some((name, negated)) = parse_name(&arg) {
+                names.push(name);
+                negations.push(negated);
+            } else {
+                pattern_span = arg[0].span();
+                pattern = Some(arg.into_iter().collect());
+            }
+        }
+        let name = &names[0];
+
+        let mut combinations = vec![Vec::new()];
+        for (name, &negated) in names.iter().zip(&negations) {
+            let mut values = split_bundle(self.get_value(name, span)?);
+            if negated {
+                // Negations are applied before substitution, so only single
+                // predicates can be negated.
+                values = match &values[..] {
+                    [value] => cfg::negate_predicate(value.clone(), span),
+                    _ => None,
+                }
+                .map(|x| vec![x])
+                .ok_or_else(|| {
+                    Error::spanned(
+                        Code::InvalidAttribute,
+                        name.span(),
+                        format!(
+                            "alias '{}' cannot be negated, since its value is \
+                             not a configuration predicate",
+                            name,
+                        ),
+                    )
+                    .note(
+                        None,
+                        format!(
+                            "alias '{}' is defined at {}",
+                            name,
+                            self.definition(&name.to_string()),
+                        ),
+                    )
+                })?;
+            }
+            combinations = combinations
+                .iter()
+                .flat_map(|combination| {
+                    values.iter().map(|value| {
+                        let mut combination = combination.clone();
+                        combination.push(value.clone());
+                        combination
+                    })
+                })
+                .collect();
+        }
+
+        let combination_count = combinations.len();
+
+        let explicit_pattern = pattern.is_some();
+        let patterns = if let Some(pattern) = pattern {
+            let pattern = self.resolve_nested(pattern, span)?;
+            if has_empty_attr(&pattern) {
+                return Err(Error::spanned(
+                    Code::Syntax,
+                    pattern_span,
+                    "pattern contains an empty attribute".to_owned(),
+                ));
+            }
+            split_bundle(self.resolve_combinators(pattern, &mut false)?)
+        } else if names.len() == 1
+            && (is_bundle(&self.get_value(name, name.span())?)
+                || name.to_string() == Self::RAW_DEFAULT_NAME)
+        {
+            Vec::new()
+        } else {
+            self.entry(Self::DEFAULT_NAME)
+                .map(|(name, entry)| self.resolve_entry(name, entry))
+                .transpose()?
+                .map(|x| split_bundle(Token::to_stream(&x.tokens, span)))
+                .unwrap_or_default()
+        };
+        for pattern in &patterns {
+            let wildcards = count_wildcards(pattern);
+            if wildcards == 0 {
+                let names: Vec<_> =
+                    names.iter().map(ToString::to_string).collect();
+                let mut error = Error::spanned(
+                    Code::Wildcards,
+                    pattern_span,
+                    format!(
+                        "pattern `{}` does not contain a wildcard, so '{}' \
+                         would be ignored",
+                        pattern,
+                        names.join("', '"),
+                    ),
+                );
+                if let Some(suggestion) = suggest_wildcard(pattern) {
+                    error = error.note(
+                        None,
+                        format!(
+                            "the wildcard may belong here: `{}`",
+                            suggestion,
+                        ),
+                    );
+                }
+                return Err(error.note(
+                    None,
+                    "each `*` in the pattern is replaced by the value of an \
+                     alias"
+                        .to_owned(),
+                ));
+            }
+            if wildcards != names.len() {
+                return Err(Error::spanned(
+                    Code::Wildcards,
+                    pattern_span,
+                    format!(
+                        "pattern `{}` has {} but {} given",
+                        pattern,
+                        pluralize(wildcards, "wildcard", "wildcards"),
+                        if wildcards > names.len() {
+                            format!(
+                                "only {}",
+                                pluralize(names.len(), "alias", "aliases")
+                            )
+                        } else {
+                            pluralize(names.len(), "alias", "aliases")
+                        },
+                    ),
+                ));
+            }
+        }
+        let attrs: Vec<_> = if patterns.is_empty() {
+            if names.len() != 1 {
+                return Err(Error::spanned(
+                    Code::Wildcards,
+                    names[1].span(),
+                    "multiple aliases require a pattern".to_owned(),
+                ));
+            }
+            combinations.into_iter().flatten().collect()
+        } else {
+            patterns
+                .iter()
+                .flat_map(|pattern| {
+                    combinations.iter().map(|values| {
+                        substitute(pattern.clone(), values, &mut 0)
+                    })
+                })
+                .collect()
+        };
+        let mut attrs: Vec<_> =
+            attrs.into_iter().map(cfg::simplify_attr).collect();
+
+        if let Some((i, attr)) =
+            attrs.iter().enumerate().find(|(_, x)| !is_valid_attr(x))
+        {
+            let mut error = Error::spanned(
+                Code::InvalidAttribute,
+                name.span(),
+                if let [name] = &names[..] {
+                    format!(
+                        "alias '{}' expands to an invalid attribute: `{}`",
+                        name, attr,
+                    )
+                } else {
+                    let names: Vec<_> =
+                        names.iter().map(ToString::to_string).collect();
+                    format!(
+                        "aliases '{}' expand to an invalid attribute: `{}`",
+                        names.join("', '"),
+                        attr,
+                    )
+                },
+            );
+            for name in &names {
+                error = error.note(
+                    Some(name.span()),
+                    format!(
+                        "alias '{}' is defined at {} as `{}`",
+                        name,
+                        self.definition(&name.to_string()),
+                        self.get_alias(name)?.text,
+                    ),
+                );
+            }
+            // Attributes are produced for each combination of values, for
+            // each pattern in order.
+            let note = match patterns.get(i / combination_count) {
+                Some(pattern) => format!(
+                    "the attribute was produced by the {}pattern `{}`",
+                    if explicit_pattern { "" } else { "default " },
+                    pattern,
+                ),
+                None => {
+                    "the attribute was produced without a pattern".to_owned()
+                }
+            };
+            return Err(error.note(Some(pattern_span), note));
+        }
+
+        if let Some(level) = level {
+            let mut lints = false;
+            for attr in &mut attrs {
+                let mut tokens: Vec<_> = attr.clone().into_iter().collect();
+                if let Some(TokenTree::Ident(x)) = tokens.first_mut() {
+                    if LINT_LEVELS.contains(&&*x.to_string()) {
+                        *x = level.clone();
+                        lints = true;
+                    }
+                }
+                *attr = tokens.into_iter().collect();
+            }
+            if !lints {
+                return Err(Error::spanned(
+                    Code::Level,
+                    level.span(),
+                    format!(
+                        "alias '{}' does not expand to lint attributes",
+                        name,
+                    ),
+                )
+                .note(
+                    None,
+                    format!(
+                        "alias '{}' is defined at {}",
+                        name,
+                        self.definition(&name.to_string()),
+                    ),
+                ));
+            }
+        }
+
+        // Explicit patterns are not documented, since they may not describe
+        // where the item is available.
+        if !explicit_pattern && self.documents(&names) {
+            // Descriptions would not be accurate for negated aliases.
+            let texts: Vec<_> = names
+                .iter()
+                .zip(&negations)
+                .filter(|(_, &negated)| !negated)
+                .filter_map(|(x, _)| self.doc_texts.get(&x.to_string()))
+                .collect();
+            if texts.is_empty() {
+                // The attribute may already be included by the value.
+                let existing: Vec<_> =
+                    attrs.iter().map(ToString::to_string).collect();
+                attrs = attrs
+                    .into_iter()
+                    .flat_map(|attr| {
+                        let doc_attr = cfg::doc_attr(&attr)
+                            .filter(|x| !existing.contains(&x.to_string()));
+                        iter::once(attr).chain(doc_attr)
+                    })
+                    .collect();
+            } else {
+                attrs.extend(
+                    texts.into_iter().map(|x| cfg::doc_text_attr(x, span)),
+                );
+            }
+        }
+
+        for attr in &attrs {
+            self.check_cfgs(cfg::attr_names(attr))?;
+            // Names listed by the "allow-cfg" directive are intended.
+            if !self.file.check_cfg_names || self.allowed_cfgs.is_some() {
+                continue;
+            }
+            for (cfg_name, similar_name) in cfg::unknown_names(attr) {
+                let mut warning = Error::spanned(
+                    Code::UnexpectedCfg,
+                    cfg_name.span(),
+                    format!(
+                        "unexpected configuration name '{}' in the expansion \
+                         of '{}'",
+                        cfg_name, name,
+                    ),
+                );
+                if let Some(similar_name) = similar_name {
+                    warning = warning.note(
+                        None,
+                        format!("a similar name exists: '{}'", similar_name),
+                    );
+                }
+                warning = warning.note(
+                    None,
+                    format!(
+                        "alias '{}' is defined at {}",
+                        name,
+                        self.definition(&name.to_string()),
+                    ),
+                );
+                warning
+                    .note(
+                        None,
+                        "custom names can be declared using the \"check-cfg\" \
+                         configuration of the \"unexpected_cfgs\" lint in \
+                         \"Cargo.toml\""
+                            .to_owned(),
+                    )
+                    .warn();
+                *warned = true;
+            }
+        }
+
+        let patterns: Vec<_> =
+            patterns.iter().map(ToString::to_string).collect();
+        let resolution = Resolution {
+            names: names.iter().map(ToString::to_string).collect(),
+            default: !explicit_pattern && !patterns.is_empty(),
+            pattern: if patterns.is_empty() {
+                "*".to_owned()
+            } else {
+                patterns.join("; ")
+            },
+            attrs: attrs
+                .iter()
+                .map(|x| Token::from_stream(x.clone()))
+                .collect(),
+        };
+        Ok((resolution, attrs))
+    }
+
+    // Returns the value of each alias and the attributes that it expands to
+    // without a pattern, in the order they are defined.
+    pub(super) fn expansions(
+        &self,
+    ) -> Result<Vec<(&str, &str, Vec<TokenStream>)>> {
+        let span = Span::call_site();
+        let patterns: Vec<_> = self
+            .entry(Self::DEFAULT_NAME)
+            .map(|(name, entry)| self.resolve_entry(name, entry))
+            .transpose()?
+            .map(|x| split_bundle(Token::to_stream(&x.tokens, span)))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|x| count_wildcards(x) == 1)
+            .collect();
+
+        let mut entries: Vec<_> = self
+            .file
+            .entries
+            .iter()
+            .filter(|(name, _)| *name != Self::DEFAULT_NAME)
+            .collect();
+        entries.sort_unstable_by_key(|(_, entry)| entry.index);
+        entries
+            .into_iter()
+            .map(|(name, entry)| {
+                let value = self.resolve_entry(name, entry)?;
+                let text = &*value.text;
+                let value = Token::to_stream(&value.tokens, span);
+                let attrs = if is_bundle(&value) || patterns.is_empty() {
+                    split_bundle(value)
+                } else {
+                    patterns
+                        .iter()
+                        .map(|x| {
+                            cfg::simplify_attr(substitute(
+                                x.clone(),
+                                slice::from_ref(&value),
+                                &mut 0,
+                            ))
+                        })
+                        .collect()
+                };
+                Ok((name.as_str(), text, attrs))
+            })
+            .collect()
+    }
+
+    // Helper items are decorated by the "allow-lints" directive.
+    pub(super) fn lint_attrs(&self) -> TokenStream {
+        if self.file.allow_lints {
+            TokenStream::from_str(Self::LINT_ATTR).expect("invalid attribute")
+        } else {
+            TokenStream::new()
+        }
+    }
+
+    // Descriptions are given by the "doc-text" directive.
+    pub(super) fn description(&self, name: &str) -> Option<&str> {
+        self.doc_texts.get(name).map(String::as_str)
+    }
+
+    // Returns the custom configuration names used by the expansions of aliases
+    // without a pattern, each with the values it is compared to. An option
+    // that is only a name has no value.
+    pub(super) fn custom_cfgs(
+        &self,
+    ) -> Result<BTreeMap<String, BTreeSet<Option<String>>>> {
+        let mut cfgs: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+        for (_, _, attrs) in self.expansions()? {
+            for attr in &attrs {
+                for (name, value) in cfg::attr_options(attr) {
+                    let name = name.to_string();
+                    if names::is_custom_name(&name) {
+                        let _ = cfgs
+                            .entry(name)
+                            .or_default()
+                            .insert(value.map(|x| x.to_string()));
+                    }
+                }
+            }
+        }
+        Ok(cfgs)
+    }
+
+    // Returns the predicates of aliases that expand to a "cfg" attribute
+    // without a pattern. Other aliases cannot be represented by "cfg_aliases".
+    pub(super) fn predicates(&self) -> Result<Vec<(&str, TokenStream)>> {
+        Ok(self
+            .expansions()?
+            .into_iter()
+            .filter(|(name, _, _)| !name.starts_with("r#"))
+            .filter_map(|(name, _, attrs)| {
+                attrs
+                    .iter()
+                    .find_map(cfg::attr_predicate)
+                    .map(|predicate| (name, predicate))
+            })
+            .collect())
+    }
+
+    pub(super) fn resolve_combinator(
+        &self,
+        operator: Ident,
+        args: TokenStream,
     ) -> Result<TokenStream> {
-        const DEFAULT_NAME: &str = "default";
+        let invocation = (operator.clone(), args.clone());
+        let mut args = args.into_iter().peekable();
+        let mut aliases = TokenStream::new();
+        while args.peek().is_some() {
+            let name = next!(args, Ident)?;
+            if !aliases.is_empty() {
+                aliases.extend(tokens!(Punct::new(',', Spacing::Alone),));
+            }
+            aliases.extend(self.get_value(&name, name.span())?);
+            self.record_usage(&name.to_string(), name.span());
+            if args.peek().is_some() {
+                let _ = next!(args, Punct, as_char => ',')?;
+            }
+        }
+
+        let mut predicate = Group::new(Delimiter::Parenthesis, aliases);
+        predicate.set_span(operator.span());
+        let span = operator.span();
+        let predicate =
+            cfg::simplify_predicate(tokens!(operator, predicate,).collect());
+        self.check_cfgs(cfg::predicate_names(predicate.clone()))?;
+
+        debug(span, || {
+            let (operator, args) = invocation;
+            format!("resolved `{}!({})` to `{}`", operator, args, predicate)
+        });
+        Ok(predicate)
+    }
 
-        let mut args = args.into_iter().fuse();
+    fn resolve_combinators(
+        &self,
+        stream: TokenStream,
+        resolved: &mut bool,
+    ) -> Result<TokenStream> {
+        let mut tokens: Vec<_> = stream.into_iter().collect();
+        let mut i = 0;
+        while i < tokens.len() {
+            if let Some((operator, args)) =
+                parse_macro_call(&tokens[i..], &["all", "any"])
+            {
+                let predicate =
+                    self.resolve_combinator(operator.clone(), args.stream())?;
+                let _ = tokens.splice(i..i + 3, predicate);
+                *resolved = true;
+                i += 1;
+            } else if let TokenTree::Group(group) = &tokens[i] {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    self.resolve_combinators(group.stream(), resolved)?,
+                );
+                new_group.set_span(group.span());
+                tokens[i] = new_group.into();
+            }
+            i += 1;
+        }
+        Ok(tokens.into_iter().collect())
+    }
+
+    // Markers written as "attr_alias(let name = alias)" bind a local name,
+    // which can also be bound to another local name.
+    pub(super) fn parse_binding<F>(
+        &self,
+        attr: &TokenStream,
+        bound: &F,
+    ) -> Result<Option<(Ident, Ident)>>
+    where
+        F: Fn(&Ident) -> Option<Ident>,
+    {
+        let mut attr = attr.clone().into_iter();
+        let (Ok(_), Ok(args)) = (
+            next!(attr, Ident, to_string => "attr_alias"),
+            next!(attr, Group, delimiter => Delimiter::Parenthesis),
+        ) else {
+            return Ok(None);
+        };
+        let mut args = args.stream().into_iter().peekable();
+        let Some(keyword) = args.next_if(|x| is_ident(x, "let")) else {
+            return Ok(None);
+        };
+        super::parse_empty(attr)?;
         let name = next!(args, Ident)?;
-        let mut pattern = args
-            .next()
-            .map(|token| {
-                if !is_comma(&token) {
-                    return Err(Error::token(&token));
-                }
+        let _ = next!(args, Punct, as_char => '=')?;
+        let written_alias = next!(args, Ident)?;
+        let _ = args.next_if(is_comma);
+        super::parse_empty(args)?;
+
+        let mut alias = bound(&written_alias).unwrap_or(written_alias.clone());
+        alias.set_span(written_alias.span());
+        let _ = self.get_alias(&alias)?;
+        debug(keyword.span(), || {
+            format!(
+                "bound '{}' to '{}' ({})",
+                name,
+                alias,
+                self.definition(&alias.to_string()),
+            )
+        });
+        Ok(Some((name, alias)))
+    }
 
-                let pattern: TokenStream =
-                    args.by_ref().take_while(|x| !is_comma(x)).collect();
+    // Each arm selects a module path when its alias is enabled and those of
+    // the arms before it are not. The arm named "_" must be last, and it is
+    // selected when none of the others are.
+    pub(super) fn resolve_mod_path(
+        &self,
+        args: TokenStream,
+        span: Span,
+    ) -> Result<Vec<TokenStream>> {
+        let mut args = args.into_iter().peekable();
+        let mut previous = Vec::new();
+        let mut attrs = Vec::new();
+        let mut fallback = false;
+        while args.peek().is_some() {
+            let name = next!(args, Ident)?;
+            if fallback {
+                return Err(Error::spanned(
+                    Code::Syntax,
+                    name.span(),
+                    "the arm for '_' must be the last".to_owned(),
+                ));
+            }
+            let _ = next!(args, Punct, as_char => '=')?;
+            let _ = next!(args, Punct, as_char => '>')?;
+            let path = next!(args, Literal)?;
+            if args.peek().is_some() {
+                let _ = next!(args, Punct, as_char => ',')?;
+            }
+
+            let condition = if name.to_string() == "_" {
+                fallback = true;
+                None
+            } else {
+                Some(self.resolve_combinator(
+                    Ident::new("all", name.span()),
+                    tokens!(name,).collect(),
+                )?)
+            };
+            let predicate =
+                cfg::branch_predicate(condition.clone(), &previous, span);
+            previous.extend(condition);
+            attrs.push(
+                tokens!(
+                    Ident::new("cfg_attr", span),
+                    Group::new(
+                        Delimiter::Parenthesis,
+                        predicate
+                            .into_iter()
+                            .chain(tokens!(
+                                Punct::new(',', Spacing::Alone),
+                                Ident::new("path", span),
+                                Punct::new('=', Spacing::Alone),
+                                path,
+                            ))
+                            .collect(),
+                    ),
+                )
+                .collect(),
+            );
+        }
+        if attrs.is_empty() {
+            return Err(Error::new(Code::Syntax, "missing module paths"));
+        }
+        Ok(attrs)
+    }
+
+    // Markers within documentation are replaced with the values of their
+    // aliases as string literals, which allows using them with "concat!".
+    fn resolve_doc(
+        &self,
+        stream: TokenStream,
+        resolved: &mut bool,
+    ) -> Result<TokenStream> {
+        let mut tokens: Vec<_> = stream.into_iter().collect();
+        let mut i = 0;
+        while i < tokens.len() {
+            if let Some((_, args)) =
+                parse_macro_call(&tokens[i..], &["attr_alias"])
+            {
+                let mut args = args.stream().into_iter().peekable();
+                let name = next!(args, Ident)?;
+                let _ = args.next_if(is_comma);
                 super::parse_empty(args)?;
-                Ok(pattern)
-            })
-            .transpose()?
-            .filter(|x| !x.is_empty());
-
-        // The default alias does not make sense to nest, as the only way to
-        // nest it would be to nest [#[attr_alias]], which already has syntax
-        // for it to be implicitly used.
-        let alias = Some(name.to_string())
-            .filter(|x| x != DEFAULT_NAME)
-            .and_then(|x| self.0.get(&x))
-            .ok_or_else(|| Error {
-                span: name.span(),
-                message: format!("unknown alias '{}'", name),
-            })?;
-        if let Some(pattern) = &mut pattern {
-            let _ = self.resolve(pattern)?;
+
+                let mut value = Literal::string(&self.get_alias(&name)?.text);
+                self.record_usage(&name.to_string(), name.span());
+                value.set_span(name.span());
+                let _ = tokens.splice(i..i + 3, tokens!(value,));
+                *resolved = true;
+            } else if let TokenTree::Group(group) = &tokens[i] {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    self.resolve_doc(group.stream(), resolved)?,
+                );
+                new_group.set_span(group.span());
+                tokens[i] = new_group.into();
+            }
+            i += 1;
         }
-        Ok(pattern
-            .map(|x| x.to_string())
-            .as_ref()
-            .or_else(|| self.0.get(DEFAULT_NAME))
-            .map(|x| x.replacen('*', alias, 1))
-            .as_ref()
-            .unwrap_or(alias)
-            .parse()
-            .expect("error parsing alias"))
+        Ok(tokens.into_iter().collect())
+    }
+
+    fn resolve_nested(
+        &self,
+        stream: TokenStream,
+        span: Span,
+    ) -> Result<TokenStream> {
+        let mut tokens = stream.into_iter().peekable();
+        let mut result = TokenStream::new();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Ident(x) if x.to_string() == "attr_alias" => {
+                    let args = next!(
+                        tokens,
+                        Group,
+                        delimiter => Delimiter::Parenthesis,
+                    )?;
+                    for (i, attr) in self
+                        .resolve_args(args.stream(), span)?
+                        .into_iter()
+                        .enumerate()
+                    {
+                        if i != 0 {
+                            result.extend(tokens!(Punct::new(
+                                ';',
+                                Spacing::Alone
+                            ),));
+                        }
+                        result.extend(attr);
+                    }
+                }
+                TokenTree::Group(group) => {
+                    let mut new_group = Group::new(
+                        group.delimiter(),
+                        self.resolve_nested(group.stream(), span)?,
+                    );
+                    new_group.set_span(group.span());
+                    result.extend(tokens!(new_group,));
+                }
+                _ => result.extend(tokens!(token,)),
+            }
+        }
+        Ok(result)
     }
 
-    pub(super) fn resolve(&self, attr: &mut TokenStream) -> Result<bool> {
+    pub(super) fn resolve(
+        &self,
+        attr: TokenStream,
+        span: Span,
+    ) -> Result<Option<Vec<TokenStream>>> {
+        // The attribute selecting module paths is also resolved here, since
+        // non-inline modules can only be annotated by it within
+        // "eval_block!" on the stable release channel.
+        let mut tokens: Vec<_> = attr.clone().into_iter().collect();
+        if let Some(TokenTree::Group(args)) = tokens.pop() {
+            if args.delimiter() == Delimiter::Parenthesis
+                && super::crate_path_len(&tokens, "mod_path")
+                    == Some(tokens.len())
+            {
+                return self.resolve_mod_path(args.stream(), span).map(Some);
+            }
+        }
+
         let mut attr_iter = attr.clone().into_iter();
-        next!(attr_iter, Ident, to_string => "attr_alias")
+        let args = next!(attr_iter, Ident, to_string => "attr_alias")
             .ok()
             .map(|_| {
                 let args = next!(
@@ -98,76 +1597,628 @@ impl Aliases {
                 super::parse_empty(attr_iter)?;
                 Ok(args.stream())
             })
-            .transpose()?
-            .map(|args| self.resolve_args(args).map(|x| *attr = x))
-            .transpose()
-            .map(|x| x.is_some())
+            .transpose()?;
+        if let Some(args) = args {
+            return self.resolve_args(args, span).map(Some);
+        }
+
+        // Aliases within unsafe attributes are resolved as if the wrapper was
+        // part of the pattern, unless the expansion already includes it.
+        if let Some((keyword, group)) = parse_unsafe(&attr) {
+            return self.resolve(group.stream(), span).map(|attrs| {
+                attrs.map(|attrs| {
+                    attrs
+                        .into_iter()
+                        .map(|attr| {
+                            if parse_unsafe(&attr).is_some() {
+                                return attr;
+                            }
+                            let mut group =
+                                Group::new(Delimiter::Parenthesis, attr);
+                            group.set_span(keyword.span());
+                            tokens!(keyword.clone(), group,).collect()
+                        })
+                        .collect()
+                })
+            });
+        }
+
+        let mut resolved = false;
+        let mut attr_iter = attr.clone().into_iter();
+        let attr = if next!(attr_iter, Ident, to_string => "doc").is_ok()
+            && next!(attr_iter, Punct, as_char => '=').is_ok()
+        {
+            self.resolve_doc(attr, &mut resolved)?
+        } else {
+            attr
+        };
+        let attr = self.resolve_combinators(attr, &mut resolved)?;
+        Ok(resolved.then(|| vec![attr]))
     }
 
-    fn parse() -> Result<Self> {
-        let mut aliases = "\n".to_owned();
+    fn read(path: &Path) -> Result<String> {
+        let mut aliases = String::new();
         let _ = OpenOptions::new()
             .read(true)
-            .open(Self::FILE)
-            .map_err(|x| Error::new_from(x, "opening alias file"))?
+            .open(path)
+            .map_err(|x| {
+                // The description of this error differs between platforms.
+                let not_found = x.kind() == io::ErrorKind::NotFound;
+                let mut error = if not_found {
+                    Error::new(Code::Reading, "alias file does not exist")
+                } else {
+                    Error::new_from(Code::Reading, x, "opening alias file")
+                }
+                .note(None, format!("path: {}", path.display()));
+                if let Ok(current_dir) = Self::current_dir() {
+                    error = error.note(
+                        None,
+                        format!(
+                            "current directory: {}",
+                            current_dir.display()
+                        ),
+                    );
+                }
+                if not_found {
+                    error = error
+                        .note(
+                            None,
+                            "create the file with aliases such as:\n    \
+                         *default=cfg(*)\n    *macos=target_os = \"macos\""
+                                .to_owned(),
+                        )
+                        .note(
+                            None,
+                            "packages built from registries only contain the \
+                         file if their manifests do not exclude it"
+                                .to_owned(),
+                        );
+                }
+                error
+            })?
             .read_to_string(&mut aliases)
-            .map_err(|x| Error::new_from(x, "reading alias file"))?;
-
-        let mut parsed_aliases = Self(HashMap::new());
-        let mut aliases = aliases.split("\n*").peekable();
-        let _ = aliases.next_if_eq(&"");
-        for alias in aliases {
-            let mut alias = alias
-                .parse::<TokenStream>()
-                .map_err(|x| Error::new_from(x, "parsing alias file"))?
-                .into_iter();
-            let alias_name = next!(alias, Ident)?;
-            let _ = next!(alias, Punct, as_char => '=')?;
-            let mut alias = alias.collect();
-            let _ = parsed_aliases.resolve(&mut alias)?;
-            if parsed_aliases
-                .0
-                .insert(alias_name.to_string(), alias.to_string())
-                .is_some()
-            {
-                return Err(Error::new("duplicate alias name in alias file"));
+            .map_err(|x| {
+                Error::new_from(Code::Reading, x, "reading alias file")
+            })?;
+        Ok(aliases)
+    }
+
+    fn parse_entry(
+        source: &str,
+        entries: &mut BTreeMap<String, Entry>,
+        chunk: &Chunk,
+    ) -> Result<()> {
+        let end = chunk.end;
+        let text = skip_trivia(&source[chunk.start..end]);
+        // Text outside of aliases can only contain comments.
+        if !chunk.starred && text.is_empty() {
+            return Ok(());
+        }
+
+        let name_offset = end - text.len();
+        let Some((name, rest)) = syntax::split_name(text) else {
+            return Err(locate(source, name_offset)(definition_error(
+                Span::call_site(),
+                "expected an alias name after `*`".to_owned(),
+            )));
+        };
+        if let Some(builtin_kind) = syntax::builtin_kind(name) {
+            let note = if builtin_kind == "keyword" {
+                "keywords cannot be used as names elsewhere, so uses of this \
+                 alias would be confusing"
+                    .to_owned()
+            } else {
+                format!(
+                    "`#[attr_alias({})]` will expand to this alias, not to the \
+                     built-in name",
+                    name,
+                )
+            };
+            locate(source, name_offset)(
+                Error::spanned(
+                    Code::Shadowing,
+                    Span::call_site(),
+                    format!(
+                        "alias '{}' shadows a built-in {} name",
+                        name, builtin_kind,
+                    ),
+                )
+                .note(None, note),
+            )
+            .warn();
+        }
+        let Some(value) = skip_trivia(rest).strip_prefix('=') else {
+            let name_end = name_offset + name.len();
+            return Err(locate(source, next_offset(source, name_end, end))(
+                definition_error(
+                    Span::call_site(),
+                    format!("expected `=` after alias name '{}'", name),
+                ),
+            ));
+        };
+        if let Some(entry) = entries.get(name) {
+            return Err(locate(source, name_offset)(
+                Error::spanned(
+                    Code::Parsing,
+                    Span::call_site(),
+                    format!("duplicate alias name '{}' in alias file", name,),
+                )
+                .note(None, format!("first defined on line {}", entry.line)),
+            ));
+        }
+
+        let entry = Entry {
+            index: entries.len(),
+            line: chunk.line,
+            value_start: end - value.len(),
+            value_end: end,
+            value: OnceLock::new(),
+        };
+        let _ = entries.insert(name.to_owned(), entry);
+        Ok(())
+    }
+
+    // Generated aliases are defined where their directive is written, so
+    // later aliases can reference them. Their values are known, so they are
+    // resolved immediately, and aliases that were already defined are kept.
+    fn add_generated(
+        entries: &mut BTreeMap<String, Entry>,
+        name: String,
+        text: String,
+        line: usize,
+    ) {
+        if entries.contains_key(&name) {
+            return;
+        }
+        let tokens = TokenStream::from_str(&text)
+            .map(Token::from_stream)
+            .expect("invalid generated value");
+        let entry = Entry {
+            index: entries.len(),
+            line,
+            value_start: 0,
+            value_end: 0,
+            value: OnceLock::from(Value { text, tokens }),
+        };
+        let _ = entries.insert(name, entry);
+    }
+
+    fn add_features(
+        entries: &mut BTreeMap<String, Entry>,
+        manifest: &str,
+        line: usize,
+    ) {
+        for feature in names::feature_names(manifest) {
+            Self::add_generated(
+                entries,
+                format!("feat_{}", feature.replace('-', "_")),
+                format!("feature = \"{}\"", feature),
+                line,
+            );
+        }
+    }
+
+    // Versions up to the minimum supported version are always available, so
+    // their conditions are constant, and they become true as it increases.
+    fn add_msrv(
+        entries: &mut BTreeMap<String, Entry>,
+        msrv: &str,
+        versions: &[&str],
+        line: usize,
+    ) {
+        Self::add_generated(
+            entries,
+            "msrv".to_owned(),
+            format!("\"{}\"", msrv),
+            line,
+        );
+        for version in versions {
+            let supported =
+                syntax::parse_version(version) <= syntax::parse_version(msrv);
+            Self::add_generated(
+                entries,
+                format!("since_{}", version.replace('.', "_")),
+                if supported { "all()" } else { "any()" }.to_owned(),
+                line,
+            );
+        }
+    }
+
+    // Values with references are only resolved when they are used, so
+    // complements are only generated for aliases whose values are already
+    // predicates. Aliases that are already negations are skipped.
+    fn add_complements(
+        source: &str,
+        entries: &mut BTreeMap<String, Entry>,
+        line: usize,
+    ) {
+        let complements: Vec<_> = entries
+            .iter()
+            .filter(|(name, _)| {
+                *name != Self::DEFAULT_NAME && !name.starts_with("not_")
+            })
+            .filter_map(|(name, entry)| {
+                let value = match entry.value.get() {
+                    Some(value) => TokenStream::from_str(&value.text).ok()?,
+                    None => {
+                        let _ = lexer::tokenize(
+                            source,
+                            entry.value_start,
+                            entry.value_end,
+                        )
+                        .ok()?;
+                        source[entry.value_start..entry.value_end]
+                            .parse()
+                            .ok()?
+                    }
+                };
+                let negation =
+                    cfg::negate_predicate(value, Span::call_site())?;
+                Some((
+                    format!("not_{}", name.strip_prefix("r#").unwrap_or(name)),
+                    cfg::simplify_predicate(negation).to_string(),
+                ))
+            })
+            .collect();
+        for (name, text) in complements {
+            Self::add_generated(entries, name, text, line);
+        }
+    }
+
+    // Each platform is an operating system, so negations and the combined
+    // alias can be derived from the list.
+    fn add_platforms(
+        entries: &mut BTreeMap<String, Entry>,
+        platforms: &[&str],
+        line: usize,
+    ) {
+        let predicates: Vec<_> = platforms
+            .iter()
+            .map(|x| format!("target_os = \"{}\"", x))
+            .collect();
+        for (platform, predicate) in platforms.iter().zip(&predicates) {
+            Self::add_generated(
+                entries,
+                (*platform).to_owned(),
+                predicate.clone(),
+                line,
+            );
+            Self::add_generated(
+                entries,
+                format!("not_{}", platform),
+                format!("not({})", predicate),
+                line,
+            );
+        }
+        Self::add_generated(
+            entries,
+            "any_supported".to_owned(),
+            format!("any({})", predicates.join(", ")),
+            line,
+        );
+    }
+
+    fn parse(source: String) -> Result<Self> {
+        let mut allowed_cfgs = None;
+        let mut doc_cfgs = None;
+        let mut doc_texts = BTreeMap::new();
+        let mut trigger = Trigger::default();
+        let mut allow_lints = false;
+        let mut check_cfg_names = false;
+        let mut entries = BTreeMap::new();
+        for item in syntax::parse(&source) {
+            let (offset, line, directive) = match item {
+                Item::Alias(chunk) => {
+                    Self::parse_entry(&source, &mut entries, &chunk)?;
+                    continue;
+                }
+                Item::Directive {
+                    offset,
+                    line,
+                    directive,
+                } => (offset, line, directive),
+            };
+            let directive = directive.map_err(|x| {
+                let error = Error::spanned(
+                    Code::Parsing,
+                    Span::call_site(),
+                    x.message,
+                );
+                let error = match x.note {
+                    Some(note) => error.note(None, note.to_owned()),
+                    None => error,
+                };
+                locate(&source, x.offset)(error)
+            })?;
+            match directive {
+                Directive::AllowCfg(names) => allowed_cfgs
+                    .get_or_insert_with(Vec::new)
+                    .extend(names.into_iter().map(ToOwned::to_owned)),
+                Directive::AllowLints => allow_lints = true,
+                Directive::CheckCfgNames => check_cfg_names = true,
+                Directive::DocCfg(names) => doc_cfgs
+                    .get_or_insert_with(Vec::new)
+                    .extend(names.into_iter().map(|(x, _)| x.to_owned())),
+                Directive::DocText {
+                    alias: (alias, _),
+                    text,
+                } => {
+                    let _ =
+                        doc_texts.insert(alias.to_owned(), text.to_owned());
+                }
+                Directive::FeatureAliases => {
+                    let path = Self::package_dir()?.join("Cargo.toml");
+                    let manifest = fs::read_to_string(&path).map_err(|x| {
+                        Error::new_from(Code::Reading, x, "reading manifest")
+                            .note(None, format!("path: {}", path.display()))
+                    })?;
+                    Self::add_features(&mut entries, &manifest, line);
+                }
+                Directive::GenerateNot => {
+                    Self::add_complements(&source, &mut entries, line);
+                }
+                Directive::MsrvAliases(versions) => {
+                    // Cargo sets this variable from the manifest, including
+                    // when it is inherited from a workspace.
+                    let msrv = env::var("CARGO_PKG_RUST_VERSION")
+                        .ok()
+                        .filter(|x| syntax::parse_version(x).is_some())
+                        .ok_or_else(|| {
+                            locate(&source, offset)(Error::spanned(
+                                Code::Parsing,
+                                Span::call_site(),
+                                "directive '#!msrv-aliases' requires the \
+                                 package to declare `rust-version`"
+                                    .to_owned(),
+                            ))
+                        })?;
+                    Self::add_msrv(&mut entries, &msrv, &versions, line);
+                }
+                Directive::Platforms(platforms) => {
+                    Self::add_platforms(&mut entries, &platforms, line);
+                }
+                Directive::TriggerAttr(attr) => {
+                    let attr = TokenStream::from_str(attr).map_err(|x| {
+                        locate(&source, offset)(Error::new_from(
+                            Code::Parsing,
+                            x,
+                            "parsing directive '#!trigger-attr'",
+                        ))
+                    })?;
+                    trigger.attrs.push(Token::from_stream(attr));
+                }
+                Directive::TriggerPrefix(prefix) => {
+                    trigger.prefix = Some(prefix.to_owned());
+                }
             }
         }
-        Ok(parsed_aliases)
+
+        Ok(Self {
+            visible: entries.len(),
+            file: Arc::new(File {
+                source,
+                entries,
+                allow_lints,
+                check_cfg_names,
+                trigger,
+            }),
+            allowed_cfgs,
+            doc_cfgs,
+            doc_texts: Arc::new(doc_texts),
+            markers: true,
+            resolutions: Mutex::new(BTreeMap::new()),
+        })
     }
 
-    pub(super) fn get() -> Result<&'static Self> {
-        static ALIASES: OnceLock<Aliases> = OnceLock::new();
+    // Each invocation checks whether the file changed, but its contents are
+    // only read again when its length or modification time differ, and it
+    // is only parsed again when its contents change. A compiler session
+    // should observe the same contents throughout, so changes are reported
+    // as errors, except by long-lived servers that expand macros for editors
+    // or when requested. Errors are cached with the contents that caused
+    // them, so each invocation reports the same error without parsing the
+    // file again, and fixing the file is observed by the next invocation.
+    pub(super) fn get() -> Result<Arc<Self>> {
+        static SNAPSHOTS: Mutex<BTreeMap<PathBuf, CachedSnapshot>> =
+            Mutex::new(BTreeMap::new());
+
+        #[cfg(feature = "testing")]
+        if let Some(table) = TABLE.with(|x| x.borrow().clone()) {
+            return Ok(table);
+        }
 
-        if ALIASES.get().is_none() {
-            let _ = ALIASES.set(Self::parse()?);
+        let path = Self::path()?;
+        let stamp = fs::metadata(&path)
+            .ok()
+            .and_then(|x| Some((x.len(), x.modified().ok()?)));
+        if let Some(cached) = SNAPSHOTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&path)
+        {
+            if stamp.is_some() && cached.stamp == stamp {
+                return cached.to_result();
+            }
         }
-        Ok(ALIASES.get().expect("error getting aliases"))
+
+        let aliases = Self::read(&path)?;
+        let mut hasher = DefaultHasher::new();
+        aliases.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let mut snapshots =
+            SNAPSHOTS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(cached) = snapshots.get_mut(&path) {
+            if cached.hash == hash {
+                cached.stamp = stamp;
+                return cached.to_result();
+            }
+            if cached.snapshot.is_ok()
+                && env::var_os("RUST_ANALYZER_INTERNALS_DO_NOT_USE").is_none()
+                && !env::var_os("ATTR_ALIAS_REFRESH").is_some_and(|x| x == "1")
+            {
+                return Err(Error::new(
+                    Code::Changed,
+                    "alias file changed during the build; build again to use \
+                     the new aliases",
+                ));
+            }
+        }
+        let result = Self::parse(aliases).map(Arc::new);
+        let snapshot =
+            result.as_ref().map(Arc::clone).map_err(CachedError::new);
+        let _ = snapshots.insert(
+            path,
+            CachedSnapshot {
+                stamp,
+                hash,
+                snapshot,
+            },
+        );
+        result
     }
 
-    pub(super) fn create_trigger() -> Result<impl Iterator<Item = TokenTree>> {
-        let mut alias_file = env::current_dir()
-            .map_err(|x| Error::new_from(x, "getting current directory"))?;
-        alias_file.push(Self::FILE);
+    // The table is parsed in the same way as the alias file, so locations in
+    // errors refer to its lines.
+    #[cfg(feature = "testing")]
+    pub(super) fn with_table<F, R>(source: String, f: F) -> Result<R>
+    where
+        F: FnOnce() -> R,
+    {
+        let table = Arc::new(Self::parse(source)?);
+        TABLE.with(|x| *x.borrow_mut() = Some(table));
+        let result = f();
+        TABLE.with(|x| *x.borrow_mut() = None);
+        Ok(result)
+    }
 
-        let alias_file = alias_file
-            .into_os_string()
-            .into_string()
-            .map_err(|_| Error::new("current directory is not utf-8"))?;
+    fn current_dir() -> Result<PathBuf> {
+        env::current_dir().map_err(|x| {
+            Error::new_from(Code::Reading, x, "getting current directory")
+        })
+    }
 
-        Ok(tokens!(
-            Ident::new("const", Span::call_site()),
-            Ident::new("_", Span::call_site()),
-            Punct::new(':', Spacing::Alone),
-            Punct::new('&', Spacing::Alone),
-            Punct::new('\'', Spacing::Joint),
-            Ident::new("static", Span::call_site()),
-            Group::new(
-                Delimiter::Bracket,
-                path!("core", "primitive", "u8").collect(),
-            ),
-            Punct::new('=', Spacing::Alone),
-        )
-        .chain(super::core_macro("include_bytes", &alias_file)))
+    // Cargo sets the manifest directory for each crate it compiles, including
+    // doctests and examples, whose working directories can differ.
+    fn package_dir() -> Result<PathBuf> {
+        env::var_os("CARGO_MANIFEST_DIR")
+            .map(|x| Ok(x.into()))
+            .unwrap_or_else(Self::current_dir)
+    }
+
+    pub(super) fn path() -> Result<PathBuf> {
+        Ok(path::join(&Self::package_dir()?, Self::FILE))
+    }
+
+    // When the package directory cannot be represented in a string literal,
+    // the file can still be included relative to the invoking source file.
+    #[allow(clippy::incompatible_msrv)]
+    #[cfg(not(attr_alias_tracked_path))]
+    #[cfg(attr_alias_span_file)]
+    fn relative_file(package_dir: &Path) -> Result<String> {
+        let source_file = Span::call_site()
+            .local_file()
+            .map(|x| Self::current_dir().map(|dir| dir.join(x)))
+            .transpose()?;
+        source_file
+            .and_then(|x| path::relative(package_dir, &x, Self::FILE))
+            .ok_or_else(|| {
+                Error::new(Code::Reading, "package directory is not utf-8")
+            })
+    }
+
+    #[cfg(not(attr_alias_tracked_path))]
+    #[cfg(not(attr_alias_span_file))]
+    fn relative_file(_: &Path) -> Result<String> {
+        Err(Error::new(Code::Reading, "package directory is not utf-8"))
+    }
+
+    // When paths can be tracked, the compiler will rebuild crates when the
+    // file changes without including it.
+    #[cfg(attr_alias_tracked_path)]
+    pub(super) fn create_trigger(
+        &self,
+    ) -> Result<impl Iterator<Item = TokenTree>> {
+        tracked::path(Self::path()?);
+        Ok(warning::take())
+    }
+
+    #[cfg(not(attr_alias_tracked_path))]
+    pub(super) fn create_trigger(
+        &self,
+    ) -> Result<impl Iterator<Item = TokenTree>> {
+        let package_dir = Self::package_dir()?;
+        let alias_file =
+            path::to_literal(&path::join(&package_dir, Self::FILE))
+                .map_or_else(|| Self::relative_file(&package_dir), Ok)?;
+
+        // Names must be unique within each module, so a hash of the location
+        // of the invocation is appended to the prefix. Invocations expanded
+        // repeatedly from the same location, such as by declarative macros,
+        // are distinguished by the number of times that it was seen. Names
+        // therefore do not depend on invocations elsewhere in the crate.
+        static LOCATIONS: Mutex<BTreeMap<String, usize>> =
+            Mutex::new(BTreeMap::new());
+
+        let trigger = &self.file.trigger;
+        let mut result: Vec<_> = self.lint_attrs().into_iter().collect();
+        let mut attrs: Vec<_> = trigger
+            .attrs
+            .iter()
+            .map(|x| Token::to_stream(x, Span::call_site()))
+            .collect();
+        let name = trigger.prefix.as_ref().map_or_else(
+            || "_".to_owned(),
+            |prefix| {
+                // Named constants are never used.
+                attrs.insert(
+                    0,
+                    tokens!(
+                        Ident::new("allow", Span::call_site()),
+                        Group::new(
+                            Delimiter::Parenthesis,
+                            tokens!(Ident::new(
+                                "dead_code",
+                                Span::call_site(),
+                            ),)
+                            .collect(),
+                        ),
+                    )
+                    .collect(),
+                );
+                let location = location(Span::call_site());
+                let mut locations =
+                    LOCATIONS.lock().unwrap_or_else(PoisonError::into_inner);
+                let count = locations.entry(location.clone()).or_default();
+                let mut hasher = DefaultHasher::new();
+                (location, *count).hash(&mut hasher);
+                *count += 1;
+                format!("{}{:016X}", prefix, hasher.finish())
+            },
+        );
+        for attr in attrs {
+            result.extend(tokens!(
+                Punct::new('#', Spacing::Joint),
+                Group::new(Delimiter::Bracket, attr),
+            ));
+        }
+
+        Ok(warning::take().chain(result).chain(
+            tokens!(
+                Ident::new("const", Span::call_site()),
+                Ident::new(&name, Span::call_site()),
+                Punct::new(':', Spacing::Alone),
+                Punct::new('&', Spacing::Alone),
+                Group::new(
+                    Delimiter::Bracket,
+                    path!("core", "primitive", "u8").collect(),
+                ),
+                Punct::new('=', Spacing::Alone),
+            )
+            .chain(super::core_macro(
+                "include_bytes",
+                Literal::string(&alias_file),
+            )),
+        ))
     }
 }
@@ -0,0 +1,765 @@
+use std::collections::BTreeSet;
+use std::slice;
+
+use super::lexer;
+use super::lexer::Kind;
+use super::lexer::Token;
+use super::names;
+use super::syntax;
+use super::syntax::Chunk;
+use super::syntax::Directive;
+use super::syntax::Item;
+
+const DEFAULT_NAME: &str = "default";
+const RAW_DEFAULT_NAME: &str = "r#default";
+
+pub(super) enum Level {
+    Error,
+    Warning,
+}
+
+// Codes are shared with the diagnostics reported by the macros, when they
+// are analogous.
+pub(super) struct Diagnostic {
+    pub(super) level: Level,
+    pub(super) code: Option<&'static str>,
+    pub(super) offset: usize,
+    pub(super) message: String,
+    pub(super) notes: Vec<String>,
+}
+
+struct Alias {
+    name: String,
+    offset: usize,
+    line: usize,
+    // Values that cannot be tokenized have already been reported.
+    value: Option<Vec<Token>>,
+    // Aliases defined by directives are not reported as unused, since they
+    // are not written by hand.
+    generated: bool,
+    // Whether the value is known to be a predicate, without resolving
+    // references.
+    predicate: bool,
+}
+
+struct Reference<'a> {
+    name: &'a str,
+    offset: usize,
+    pattern: bool,
+}
+
+fn split_args(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut args: Vec<_> = tokens.split(|x| x.is_punct(',')).collect();
+    if args.last().is_some_and(|x| x.is_empty()) {
+        let _ = args.pop();
+    }
+    args
+}
+
+fn ident(tokens: &[Token]) -> Option<&str> {
+    match tokens {
+        [Token {
+            kind: Kind::Ident(x),
+            ..
+        }] => Some(x),
+        _ => None,
+    }
+}
+
+// Names given to markers can be negated, so the offset of the name is also
+// returned.
+fn marker_name(tokens: &[Token]) -> Option<(&str, usize)> {
+    let name = match tokens {
+        [bang, name] if bang.is_punct('!') => slice::from_ref(name),
+        [operator, operand] if operator.is_ident("not") => {
+            operand.group('(')?
+        }
+        _ => tokens,
+    };
+    ident(name).map(|x| (x, name[0].offset))
+}
+
+// Collects the aliases referenced by markers and combinators.
+fn references<'a>(tokens: &'a [Token], references: &mut Vec<Reference<'a>>) {
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1);
+        if token.is_ident("attr_alias") {
+            if let Some(args) = next.and_then(|x| x.group('(')) {
+                let mut names = Vec::new();
+                let mut pattern = false;
+                for arg in split_args(args) {
+                    match (marker_name(arg), arg) {
+                        (Some(name), _) if !pattern => names.push(name),
+                        // Lint levels do not reference aliases.
+                        (_, [name, equals, _])
+                            if name.is_ident("level")
+                                && equals.is_punct('=') => {}
+                        _ => pattern = true,
+                    }
+                }
+                references.extend(names.into_iter().map(|(name, offset)| {
+                    Reference {
+                        name,
+                        offset,
+                        pattern,
+                    }
+                }));
+            }
+        } else if (token.is_ident("all") || token.is_ident("any"))
+            && next.is_some_and(|x| x.is_punct('!'))
+        {
+            if let Some(args) = tokens.get(i + 2).and_then(|x| x.group('(')) {
+                references.extend(split_args(args).into_iter().filter_map(
+                    |arg| {
+                        ident(arg).map(|name| Reference {
+                            name,
+                            offset: arg[0].offset,
+                            pattern: true,
+                        })
+                    },
+                ));
+            }
+        }
+        if let Kind::Group(_, tokens) = &token.kind {
+            self::references(tokens, references);
+        }
+    }
+}
+
+// Tokens substituted during expansion cannot be checked until then.
+fn is_concrete(tokens: &[Token]) -> bool {
+    tokens.iter().all(|token| match &token.kind {
+        Kind::Group(_, tokens) => is_concrete(tokens),
+        Kind::Ident(x) => x != "attr_alias",
+        Kind::Punct(x) => !matches!(x, '*' | '!'),
+        Kind::Literal => true,
+    })
+}
+
+// Returns the offset of the first invalid token, or the given offset if the
+// predicate is empty.
+fn predicate(
+    tokens: &[Token],
+    offset: usize,
+    names: &mut Vec<(String, usize)>,
+) -> Result<(), usize> {
+    match tokens {
+        [operator, operands]
+            if ["all", "any", "not"].iter().any(|x| operator.is_ident(x)) =>
+        {
+            let operands =
+                split_args(operands.group('(').ok_or(operands.offset)?);
+            if operator.is_ident("not") && operands.len() != 1 {
+                return Err(operator.offset);
+            }
+            for operand in operands {
+                predicate(operand, operator.offset, names)?;
+            }
+            Ok(())
+        }
+        [Token {
+            kind: Kind::Ident(name),
+            offset,
+        }] => {
+            names.push((name.clone(), *offset));
+            Ok(())
+        }
+        [Token {
+            kind: Kind::Ident(name),
+            offset,
+        }, equals, Token {
+            kind: Kind::Literal,
+            ..
+        }] if equals.is_punct('=') => {
+            names.push((name.clone(), *offset));
+            Ok(())
+        }
+        [token, ..] => Err(token.offset),
+        [] => Err(offset),
+    }
+}
+
+// Returns the predicates of "cfg" and "cfg_attr" attributes.
+fn predicates<'a>(tokens: &'a [Token], predicates: &mut Vec<&'a [Token]>) {
+    for (i, token) in tokens.iter().enumerate() {
+        let args = tokens.get(i + 1).and_then(|x| x.group('('));
+        if let Some(args) = args {
+            if token.is_ident("cfg") {
+                predicates.push(args);
+            } else if token.is_ident("cfg_attr") {
+                if let Some(predicate) = split_args(args).first() {
+                    predicates.push(predicate);
+                }
+            }
+        }
+        if let Kind::Group(_, tokens) = &token.kind {
+            self::predicates(tokens, predicates);
+        }
+    }
+}
+
+pub(super) struct Checker<'a> {
+    source: &'a str,
+    aliases: Vec<Alias>,
+    allowed_cfgs: Option<Vec<String>>,
+    check_cfg_names: bool,
+    doc_cfgs: Vec<(&'a str, usize)>,
+    doc_texts: Vec<(&'a str, usize)>,
+    declared_names: Vec<String>,
+    feature_names: Vec<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Checker<'a> {
+    fn report(
+        &mut self,
+        level: Level,
+        code: Option<&'static str>,
+        offset: usize,
+        message: String,
+    ) -> &mut Vec<String> {
+        self.diagnostics.push(Diagnostic {
+            level,
+            code,
+            offset,
+            message,
+            notes: Vec::new(),
+        });
+        &mut self
+            .diagnostics
+            .last_mut()
+            .expect("missing diagnostic")
+            .notes
+    }
+
+    fn skip_trivia(&mut self, offset: usize, end: usize) -> Option<usize> {
+        match lexer::skip_trivia(&self.source[..end], offset) {
+            Ok(offset) => Some(offset),
+            Err(error) => {
+                let _ = self.report(
+                    Level::Error,
+                    Some("AA0008"),
+                    error.offset,
+                    error.message,
+                );
+                None
+            }
+        }
+    }
+
+    // Definitions are parsed as they are by the macros, but values are also
+    // tokenized, since they are not checked otherwise until they are used.
+    fn parse_alias(&mut self, chunk: &Chunk) {
+        let end = chunk.end;
+        let Some(name_offset) = self.skip_trivia(chunk.start, end) else {
+            return;
+        };
+        if !chunk.starred && name_offset == end {
+            return;
+        }
+
+        let Some((name, _)) =
+            syntax::split_name(&self.source[name_offset..end])
+        else {
+            let _ = self.report(
+                Level::Error,
+                Some("AA0008"),
+                name_offset,
+                "expected an alias name after `*`".to_owned(),
+            );
+            return;
+        };
+
+        if let Some(builtin_kind) = syntax::builtin_kind(name) {
+            let _ = self.report(
+                Level::Warning,
+                Some("AA0011"),
+                name_offset,
+                format!(
+                    "alias '{}' shadows a built-in {} name",
+                    name, builtin_kind,
+                ),
+            );
+        }
+
+        let name_end = name_offset + name.len();
+        let Some(equals) = self.skip_trivia(name_end, end) else {
+            return;
+        };
+        if !self.source[equals..end].starts_with('=') {
+            let _ = self.report(
+                Level::Error,
+                Some("AA0008"),
+                equals,
+                format!("expected `=` after alias name '{}'", name),
+            );
+            return;
+        }
+        if let Some(alias) = self.aliases.iter().find(|x| x.name == name) {
+            let line = alias.line;
+            self.report(
+                Level::Error,
+                Some("AA0008"),
+                name_offset,
+                format!("duplicate alias name '{}' in alias file", name),
+            )
+            .push(format!("first defined on line {}", line));
+            return;
+        }
+
+        let value = match lexer::tokenize(self.source, equals + 1, end) {
+            Ok(value) => {
+                if value.is_empty() {
+                    let _ = self.report(
+                        Level::Error,
+                        Some("AA0008"),
+                        name_offset,
+                        format!("alias '{}' has no value", name),
+                    );
+                }
+                Some(value)
+            }
+            Err(error) => {
+                let _ = self.report(
+                    Level::Error,
+                    Some("AA0008"),
+                    error.offset,
+                    error.message,
+                );
+                None
+            }
+        };
+        let predicate = value.as_deref().is_some_and(|x| {
+            is_concrete(x)
+                && predicate(x, name_offset, &mut Vec::new()).is_ok()
+        });
+        self.aliases.push(Alias {
+            name: name.to_owned(),
+            offset: name_offset,
+            line: chunk.line,
+            value,
+            generated: false,
+            predicate,
+        });
+    }
+
+    // Values of generated aliases are not tokenized, since they are always
+    // valid. Aliases that were already defined are kept instead.
+    fn generate(
+        &mut self,
+        name: String,
+        offset: usize,
+        line: usize,
+        predicate: bool,
+    ) {
+        if self.aliases.iter().all(|x| x.name != name) {
+            self.aliases.push(Alias {
+                name,
+                offset,
+                line,
+                value: None,
+                generated: true,
+                predicate,
+            });
+        }
+    }
+
+    fn parse_directive(
+        &mut self,
+        offset: usize,
+        line: usize,
+        directive: Directive<'a>,
+    ) {
+        match directive {
+            Directive::AllowCfg(names) => self
+                .allowed_cfgs
+                .get_or_insert_with(Vec::new)
+                .extend(names.into_iter().map(ToOwned::to_owned)),
+            Directive::AllowLints
+            | Directive::TriggerAttr(_)
+            | Directive::TriggerPrefix(_) => {}
+            Directive::CheckCfgNames => self.check_cfg_names = true,
+            Directive::DocCfg(names) => self.doc_cfgs.extend(names),
+            Directive::DocText { alias, .. } => self.doc_texts.push(alias),
+            Directive::FeatureAliases => {
+                for feature in self.feature_names.clone() {
+                    self.generate(
+                        format!("feat_{}", feature.replace('-', "_")),
+                        offset,
+                        line,
+                        true,
+                    );
+                }
+            }
+            Directive::GenerateNot => {
+                let complements: Vec<_> = self
+                    .aliases
+                    .iter()
+                    .filter(|x| {
+                        x.predicate
+                            && x.name != DEFAULT_NAME
+                            && !x.name.starts_with("not_")
+                    })
+                    .map(|x| {
+                        let name =
+                            x.name.strip_prefix("r#").unwrap_or(&x.name);
+                        format!("not_{}", name)
+                    })
+                    .collect();
+                for name in complements {
+                    self.generate(name, offset, line, true);
+                }
+            }
+            Directive::MsrvAliases(versions) => {
+                self.generate("msrv".to_owned(), offset, line, false);
+                for version in versions {
+                    self.generate(
+                        format!("since_{}", version.replace('.', "_")),
+                        offset,
+                        line,
+                        true,
+                    );
+                }
+            }
+            Directive::Platforms(platforms) => {
+                for platform in platforms {
+                    self.generate(platform.to_owned(), offset, line, true);
+                    self.generate(
+                        format!("not_{}", platform),
+                        offset,
+                        line,
+                        true,
+                    );
+                }
+                self.generate("any_supported".to_owned(), offset, line, true);
+            }
+        }
+    }
+
+    fn parse(&mut self) {
+        for item in syntax::parse(self.source) {
+            match item {
+                Item::Alias(chunk) => self.parse_alias(&chunk),
+                Item::Directive {
+                    offset,
+                    line,
+                    directive: Ok(directive),
+                } => self.parse_directive(offset, line, directive),
+                Item::Directive {
+                    directive: Err(error),
+                    ..
+                } => {
+                    let notes = self.report(
+                        Level::Error,
+                        Some("AA0008"),
+                        error.offset,
+                        error.message,
+                    );
+                    notes.extend(error.note.map(ToOwned::to_owned));
+                }
+            }
+        }
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.aliases.iter().position(|x| x.name == name)
+    }
+
+    fn check_references(&mut self) -> Vec<Vec<usize>> {
+        let mut diagnostics = Vec::new();
+        let mut edges = vec![Vec::new(); self.aliases.len()];
+        let default = self.index(DEFAULT_NAME);
+        for (i, alias) in self.aliases.iter().enumerate() {
+            let mut found = Vec::new();
+            references(alias.value.as_deref().unwrap_or_default(), &mut found);
+            for reference in found {
+                if !reference.pattern {
+                    edges[i].extend(default);
+                }
+                let name = if reference.name == RAW_DEFAULT_NAME {
+                    DEFAULT_NAME
+                } else if reference.name == DEFAULT_NAME {
+                    ""
+                } else {
+                    reference.name
+                };
+                let Some(index) = self.index(name) else {
+                    let mut notes = Vec::new();
+                    if reference.name == DEFAULT_NAME {
+                        notes.push(
+                            "the default alias can only be referenced as \
+                             `r#default`"
+                                .to_owned(),
+                        );
+                    }
+                    diagnostics.push(Diagnostic {
+                        level: Level::Error,
+                        code: Some("AA0003"),
+                        offset: reference.offset,
+                        message: format!("unknown alias '{}'", reference.name),
+                        notes,
+                    });
+                    continue;
+                };
+                edges[i].push(index);
+
+                // The default alias is a pattern, so it can reference aliases
+                // anywhere.
+                if index == i {
+                    diagnostics.push(Diagnostic {
+                        level: Level::Error,
+                        code: Some("AA0003"),
+                        offset: reference.offset,
+                        message: format!(
+                            "alias '{}' references itself",
+                            alias.name
+                        ),
+                        notes: Vec::new(),
+                    });
+                } else if index > i && Some(i) != default {
+                    diagnostics.push(Diagnostic {
+                        level: Level::Error,
+                        code: Some("AA0003"),
+                        offset: reference.offset,
+                        message: format!(
+                            "alias '{}' references '{}', which is defined \
+                             after it",
+                            alias.name, reference.name,
+                        ),
+                        notes: vec!["referenced aliases must be listed first"
+                            .to_owned()],
+                    });
+                }
+            }
+        }
+        self.diagnostics.extend(diagnostics);
+        edges
+    }
+
+    // Other aliases can only reference those before them, so cycles must
+    // pass through the default alias.
+    fn check_cycles(&mut self, edges: &[Vec<usize>]) {
+        fn find(
+            edges: &[Vec<usize>],
+            target: usize,
+            path: &mut Vec<usize>,
+            visited: &mut BTreeSet<usize>,
+        ) -> bool {
+            let current = *path.last().expect("missing alias");
+            for &next in &edges[current] {
+                if next == target && path.len() > 1 {
+                    path.push(next);
+                    return true;
+                }
+                if next != current && visited.insert(next) {
+                    path.push(next);
+                    if find(edges, target, path, visited) {
+                        return true;
+                    }
+                    let _ = path.pop();
+                }
+            }
+            false
+        }
+
+        let Some(default) = self.index(DEFAULT_NAME) else {
+            return;
+        };
+        let mut path = vec![default];
+        if find(edges, default, &mut path, &mut BTreeSet::new()) {
+            let path: Vec<_> =
+                path.iter().map(|&x| &*self.aliases[x].name).collect();
+            let message = format!("cyclic reference: {}", path.join(" -> "));
+            let offset = self.aliases[default].offset;
+            let _ = self.report(Level::Error, Some("AA0003"), offset, message);
+        }
+    }
+
+    fn check_names(&mut self, alias: &str, names: Vec<(String, usize)>) {
+        for (name, offset) in names {
+            let name = name.as_str();
+            if let Some(allowed_cfgs) = &self.allowed_cfgs {
+                if !allowed_cfgs.iter().any(|x| x == name) {
+                    let note =
+                        format!("allowed names: {}", allowed_cfgs.join(", "));
+                    self.report(
+                        Level::Error,
+                        Some("AA0015"),
+                        offset,
+                        format!(
+                            "configuration name '{}' is not allowed by the \
+                             alias file",
+                            name,
+                        ),
+                    )
+                    .push(note);
+                }
+                continue;
+            }
+            if !self.check_cfg_names
+                || !names::is_custom_name(name)
+                || self.declared_names.iter().any(|x| x == name)
+            {
+                continue;
+            }
+            let similar_name = names::WELL_KNOWN_NAMES
+                .iter()
+                .copied()
+                .chain(self.declared_names.iter().map(String::as_str))
+                .map(|x| (names::distance(name, x), x))
+                .filter(|&(distance, _)| distance <= (name.len() / 3).max(1))
+                .min()
+                .map(|(_, x)| x.to_owned());
+            let notes = self.report(
+                Level::Warning,
+                Some("AA0012"),
+                offset,
+                format!(
+                    "unexpected configuration name '{}' in alias '{}'",
+                    name, alias,
+                ),
+            );
+            if let Some(similar_name) = similar_name {
+                notes.push(format!(
+                    "a similar name exists: '{}'",
+                    similar_name
+                ));
+            }
+        }
+    }
+
+    // Values are checked as predicates when the default pattern would
+    // substitute them into one and they cannot be anything else.
+    fn check_cfgs(&mut self) {
+        let default_predicate = self
+            .aliases
+            .iter()
+            .find(|x| x.name == DEFAULT_NAME)
+            .and_then(|x| x.value.as_deref())
+            .is_some_and(|value| {
+                let mut found = Vec::new();
+                predicates(value, &mut found);
+                found.iter().any(|x| !is_concrete(x))
+            });
+
+        let mut diagnostics = Vec::new();
+        let mut alias_names = Vec::new();
+        for alias in &self.aliases {
+            let Some(value) = &alias.value else {
+                continue;
+            };
+            let mut found = Vec::new();
+            predicates(value, &mut found);
+            if default_predicate && alias.name != DEFAULT_NAME {
+                let is_predicate = match &value[..] {
+                    [operator, operands] => {
+                        ["all", "any", "not"]
+                            .iter()
+                            .any(|x| operator.is_ident(x))
+                            && operands.group('(').is_some()
+                    }
+                    [_, equals, _] => equals.is_punct('='),
+                    _ => false,
+                };
+                if is_predicate {
+                    found.push(value);
+                }
+            }
+
+            let mut names = Vec::new();
+            for predicate in found.into_iter().filter(|x| is_concrete(x)) {
+                let offset =
+                    predicate.first().map_or(alias.offset, |x| x.offset);
+                if let Err(offset) =
+                    self::predicate(predicate, offset, &mut names)
+                {
+                    diagnostics.push(Diagnostic {
+                        level: Level::Error,
+                        code: Some("AA0006"),
+                        offset,
+                        message: format!(
+                            "invalid configuration predicate in alias '{}'",
+                            alias.name,
+                        ),
+                        notes: Vec::new(),
+                    });
+                }
+            }
+            alias_names.push((alias.name.clone(), names));
+        }
+        self.diagnostics.extend(diagnostics);
+        for (alias, names) in alias_names {
+            self.check_names(&alias, names);
+        }
+    }
+
+    fn check_unused(&mut self, uses: &BTreeSet<String>, edges: &[Vec<usize>]) {
+        let referenced: BTreeSet<_> =
+            edges.iter().flatten().copied().collect();
+        let unused: Vec<_> = self
+            .aliases
+            .iter()
+            .enumerate()
+            .filter(|&(i, x)| {
+                x.name != DEFAULT_NAME
+                    && !x.generated
+                    && !referenced.contains(&i)
+                    && !uses.contains(&x.name)
+            })
+            .map(|(_, x)| {
+                (x.offset, format!("alias '{}' is never used", x.name))
+            })
+            .collect();
+        for (offset, message) in unused {
+            let _ = self.report(Level::Warning, None, offset, message);
+        }
+
+        let unknown: Vec<_> = self
+            .doc_cfgs
+            .iter()
+            .map(|x| ("doc-cfg", x))
+            .chain(self.doc_texts.iter().map(|x| ("doc-text", x)))
+            .filter(|(_, (name, _))| self.index(name).is_none())
+            .map(|(directive, &(name, offset))| {
+                (
+                    offset,
+                    format!(
+                        "unknown alias '{}' in directive '#!{}'",
+                        name, directive,
+                    ),
+                )
+            })
+            .collect();
+        for (offset, message) in unknown {
+            let _ =
+                self.report(Level::Warning, Some("AA0003"), offset, message);
+        }
+    }
+}
+
+// Checks the alias file, given the names used by markers in the package.
+pub(super) fn check(
+    source: &str,
+    declared_names: Vec<String>,
+    feature_names: Vec<String>,
+    uses: &BTreeSet<String>,
+) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        source,
+        aliases: Vec::new(),
+        allowed_cfgs: None,
+        check_cfg_names: false,
+        doc_cfgs: Vec::new(),
+        doc_texts: Vec::new(),
+        declared_names,
+        feature_names,
+        diagnostics: Vec::new(),
+    };
+    checker.parse();
+    let edges = checker.check_references();
+    checker.check_cycles(&edges);
+    checker.check_cfgs();
+    checker.check_unused(uses, &edges);
+    checker.diagnostics.sort_by_key(|x| x.offset);
+    checker.diagnostics
+}
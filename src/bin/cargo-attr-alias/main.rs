@@ -0,0 +1,216 @@
+//! Checks the alias file of a package without compiling it.
+//!
+//! This binary is provided by the **cli** feature. It is usually run as a
+//! Cargo subcommand from the directory of a package, or given the directory
+//! as an argument:
+//!
+//! ```text
+//! cargo attr-alias [PACKAGE_DIR]
+//! ```
+//!
+//! The file is checked for syntax errors, duplicate names, references to
+//! unknown or later aliases, cycles through the default alias, and invalid
+//! configuration predicates. Warnings are reported for names that shadow
+//! built-in names, unexpected configuration names, and aliases that are never
+//! used by the package. Errors cause the process to exit with a failure
+//! status, so it can be used as a pre-commit hook.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+
+mod check;
+use check::Level;
+
+#[path = "../../lexer.rs"]
+mod lexer;
+
+#[path = "../../names.rs"]
+mod names;
+
+// Arguments of some directives are only used by the macros.
+#[allow(dead_code)]
+#[path = "../../syntax.rs"]
+mod syntax;
+
+// This path must match the one read by the macros.
+const FILE: &str = "src/attr-aliases.txt";
+
+const SOURCE_DIRECTORIES: &[&str] = &["benches", "examples", "src", "tests"];
+
+const USAGE: &str = "usage: cargo attr-alias [PACKAGE_DIR]";
+
+// Markers are found without parsing the source, so uses within comments are
+// also counted. Aliases are only reported as unused when no text in the
+// package could refer to them.
+fn find_uses(source: &str, uses: &mut BTreeSet<String>) {
+    for prefix in [
+        "[alias(",
+        "attr_alias(",
+        "any!(",
+        "all!(",
+        "apply(",
+        "cfg_flags!(",
+        "compile_error_unless!(",
+        "mod_path(",
+    ] {
+        for (i, _) in source.match_indices(prefix) {
+            let mut rest = &source[i + prefix.len()..];
+            loop {
+                rest = rest.trim_start();
+                // Bindings are followed by the alias that they name.
+                if let Some((_, alias)) =
+                    rest.strip_prefix("let ").and_then(|x| x.split_once('='))
+                {
+                    rest = alias.trim_start();
+                }
+                // Markers can negate names.
+                let call = rest.strip_prefix("not(");
+                let name = call
+                    .or_else(|| rest.strip_prefix('!'))
+                    .map_or(rest, str::trim_start);
+                let name = name.strip_prefix("r#").unwrap_or(name);
+                let len = name
+                    .find(|x: char| !x.is_alphanumeric() && x != '_')
+                    .unwrap_or(name.len());
+                let mut after = name[len..].trim_start();
+                if call.is_some() {
+                    let Some(next) = after.strip_prefix(')') else {
+                        break;
+                    };
+                    after = next.trim_start();
+                }
+                // Arms of "mod_path" are followed by their paths.
+                if let Some(path) = after.strip_prefix("=>") {
+                    let Some(path) = path.trim_start().strip_prefix('"')
+                    else {
+                        break;
+                    };
+                    let Some(end) = path.find('"') else {
+                        break;
+                    };
+                    after = path[end + 1..].trim_start();
+                }
+                if len == 0 || !after.starts_with([',', ')']) {
+                    break;
+                }
+                let _ = uses.insert(name[..len].to_owned());
+                if after.starts_with(')') {
+                    break;
+                }
+                rest = &after[1..];
+            }
+        }
+    }
+}
+
+fn read_uses(path: &Path, uses: &mut BTreeSet<String>) -> io::Result<()> {
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|x| x != "target") {
+                read_uses(&path, uses)?;
+            }
+        } else if path.extension().is_some_and(|x| x == "rs") {
+            find_uses(&fs::read_to_string(&path)?, uses);
+        }
+    }
+    Ok(())
+}
+
+fn locate(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |x| x + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+fn run(directory: &Path) -> Result<bool, String> {
+    let path = directory.join(FILE);
+    let source = fs::read_to_string(&path)
+        .map_err(|x| format!("error reading {}: {}", path.display(), x))?;
+    let manifest =
+        fs::read_to_string(directory.join("Cargo.toml")).unwrap_or_default();
+    let declared_names = names::declared_names(&manifest);
+    let feature_names = names::feature_names(&manifest);
+
+    let mut uses = BTreeSet::new();
+    for name in SOURCE_DIRECTORIES {
+        let path = directory.join(name);
+        if path.is_dir() {
+            read_uses(&path, &mut uses).map_err(|x| {
+                format!("error reading {}: {}", path.display(), x)
+            })?;
+        }
+    }
+
+    let diagnostics =
+        check::check(&source, declared_names, feature_names, &uses);
+    let mut errors = 0_usize;
+    for diagnostic in &diagnostics {
+        let level = match diagnostic.level {
+            Level::Error => {
+                errors += 1;
+                "error"
+            }
+            Level::Warning => "warning",
+        };
+        let code = diagnostic
+            .code
+            .map(|x| format!("[{}] ", x))
+            .unwrap_or_default();
+        let (line, column) = locate(&source, diagnostic.offset);
+        eprintln!(
+            "{}: {}{}:{}:{}: {}",
+            level, code, FILE, line, column, diagnostic.message,
+        );
+        for note in &diagnostic.notes {
+            eprintln!("  = note: {}", note);
+        }
+    }
+
+    let warnings = diagnostics.len() - errors;
+    if !diagnostics.is_empty() {
+        eprintln!(
+            "{}: {} {}, {} {}",
+            FILE,
+            errors,
+            if errors == 1 { "error" } else { "errors" },
+            warnings,
+            if warnings == 1 { "warning" } else { "warnings" },
+        );
+    }
+    Ok(errors == 0)
+}
+
+fn main() -> ExitCode {
+    // Cargo passes the name of the subcommand as the first argument.
+    let mut args = env::args_os().skip(1).peekable();
+    let _ = args.next_if(|x| x == "attr-alias");
+    let directory = args.next();
+    if directory
+        .as_ref()
+        .is_some_and(|x| x == "-h" || x == "--help" || x == "help")
+    {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    if args.next().is_some() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    match run(Path::new(&directory.unwrap_or_else(|| ".".into()))) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
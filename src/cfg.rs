@@ -0,0 +1,512 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use proc_macro::Delimiter;
+use proc_macro::Group;
+use proc_macro::Ident;
+use proc_macro::Literal;
+use proc_macro::Punct;
+use proc_macro::Spacing;
+use proc_macro::Span;
+use proc_macro::TokenStream;
+use proc_macro::TokenTree;
+
+use super::is_comma;
+use super::names;
+
+enum Predicate {
+    Operator(Ident, Span, Vec<Self>),
+    Option(Vec<TokenTree>),
+}
+
+impl Predicate {
+    fn parse(tokens: Vec<TokenTree>) -> Option<Self> {
+        if let [TokenTree::Ident(operator), TokenTree::Group(operands)] =
+            &tokens[..]
+        {
+            let name = operator.to_string();
+            if matches!(&*name, "all" | "any" | "not")
+                && operands.delimiter() == Delimiter::Parenthesis
+            {
+                let operands = Self::parse_list(operands.stream())?;
+                if name == "not" && operands.len() != 1 {
+                    return None;
+                }
+                return Some(Self::Operator(
+                    operator.clone(),
+                    tokens[1].span(),
+                    operands,
+                ));
+            }
+        }
+        (!tokens.is_empty()).then_some(Self::Option(tokens))
+    }
+
+    fn parse_list(predicates: TokenStream) -> Option<Vec<Self>> {
+        let mut predicates = predicates.into_iter().peekable();
+        let mut result = Vec::new();
+        while predicates.peek().is_some() {
+            result.push(Self::parse(
+                predicates.by_ref().take_while(|x| !is_comma(x)).collect(),
+            )?);
+        }
+        Some(result)
+    }
+
+    fn options(&self, options: &mut Vec<(Ident, Option<Literal>)>) {
+        match self {
+            Self::Operator(_, _, operands) => {
+                for operand in operands {
+                    operand.options(options);
+                }
+            }
+            Self::Option(tokens) => match &tokens[..] {
+                [TokenTree::Ident(name), TokenTree::Punct(punct), TokenTree::Literal(value)]
+                    if punct.as_char() == '=' =>
+                {
+                    options.push((name.clone(), Some(value.clone())));
+                }
+                [TokenTree::Ident(name), ..] => {
+                    options.push((name.clone(), None));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            Self::Operator(_, _, operands) => {
+                operands.iter().all(Self::is_valid)
+            }
+            Self::Option(tokens) => match &tokens[..] {
+                [TokenTree::Ident(_)] => true,
+                [TokenTree::Ident(_), TokenTree::Punct(punct), TokenTree::Literal(_)] => {
+                    punct.as_char() == '='
+                }
+                _ => false,
+            },
+        }
+    }
+
+    fn required_options(
+        &self,
+        options: &mut Vec<(Ident, Option<Literal>)>,
+    ) -> Result<(), &Self> {
+        match self {
+            Self::Operator(operator, _, operands)
+                if operator.to_string() == "all" =>
+            {
+                for operand in operands {
+                    operand.required_options(options)?;
+                }
+                Ok(())
+            }
+            Self::Option(_) if self.is_valid() => {
+                self.options(options);
+                Ok(())
+            }
+            _ => Err(self),
+        }
+    }
+
+    fn simplify(self) -> Self {
+        let Self::Operator(operator, span, operands) = self else {
+            return self;
+        };
+        let name = operator.to_string();
+        let mut operands = operands.into_iter().map(Self::simplify);
+        if name == "not" {
+            return match operands.next().expect("missing operand") {
+                Self::Operator(x, _, mut operands)
+                    if x.to_string() == name =>
+                {
+                    operands.pop().expect("missing operand")
+                }
+                operand => Self::Operator(operator, span, vec![operand]),
+            };
+        }
+
+        let mut simplified: Vec<Self> = Vec::new();
+        let mut strings = Vec::new();
+        for operand in operands {
+            let nested = match operand {
+                Self::Operator(x, _, operands) if x.to_string() == name => {
+                    operands
+                }
+                operand => vec![operand],
+            };
+            for operand in nested {
+                let string = operand.to_tokens().to_string();
+                if !strings.contains(&string) {
+                    strings.push(string);
+                    simplified.push(operand);
+                }
+            }
+        }
+        if simplified.len() == 1 {
+            return simplified.pop().expect("missing operand");
+        }
+        Self::Operator(operator, span, simplified)
+    }
+
+    fn to_tokens(&self) -> TokenStream {
+        match self {
+            Self::Operator(operator, span, operands) => {
+                let mut stream = TokenStream::new();
+                for (i, operand) in operands.iter().enumerate() {
+                    if i != 0 {
+                        stream
+                            .extend(tokens!(Punct::new(',', Spacing::Alone),));
+                    }
+                    stream.extend(operand.to_tokens());
+                }
+                let mut operands = Group::new(Delimiter::Parenthesis, stream);
+                operands.set_span(*span);
+                tokens!(operator.clone(), operands,).collect()
+            }
+            Self::Option(tokens) => tokens.iter().cloned().collect(),
+        }
+    }
+}
+
+pub(super) fn simplify_predicate(predicate: TokenStream) -> TokenStream {
+    match Predicate::parse_list(predicate.clone()) {
+        Some(mut predicates) if predicates.len() == 1 => predicates
+            .pop()
+            .expect("missing predicate")
+            .simplify()
+            .to_tokens(),
+        _ => predicate,
+    }
+}
+
+// Returns the predicate for a branch of a chain, which excludes the
+// conditions of the branches before it. A branch without a condition is
+// taken when none of the others are.
+pub(super) fn branch_predicate(
+    condition: Option<TokenStream>,
+    previous: &[TokenStream],
+    span: Span,
+) -> TokenStream {
+    let mut predicate = condition.unwrap_or_default();
+    if !previous.is_empty() {
+        let mut any = TokenStream::new();
+        for condition in previous {
+            if !any.is_empty() {
+                any.extend(tokens!(Punct::new(',', Spacing::Alone),));
+            }
+            any.extend(condition.clone());
+        }
+        if !predicate.is_empty() {
+            predicate.extend(tokens!(Punct::new(',', Spacing::Alone),));
+        }
+        predicate.extend(tokens!(
+            Ident::new("not", span),
+            Group::new(
+                Delimiter::Parenthesis,
+                tokens!(
+                    Ident::new("any", span),
+                    Group::new(Delimiter::Parenthesis, any),
+                )
+                .collect(),
+            ),
+        ));
+    }
+    simplify_predicate(
+        tokens!(
+            Ident::new("all", span),
+            Group::new(Delimiter::Parenthesis, predicate),
+        )
+        .collect(),
+    )
+}
+
+// Applies a function to the predicates of all "cfg" and "cfg_attr"
+// attributes, which can be nested within other attributes (e.g.,
+// "doc(cfg(...))").
+fn map_predicates<F>(attr: TokenStream, f: &mut F) -> TokenStream
+where
+    F: FnMut(TokenStream) -> TokenStream,
+{
+    let mut tokens: Vec<_> = attr.into_iter().collect();
+    for i in 0..tokens.len() {
+        let TokenTree::Group(group) = &tokens[i] else {
+            continue;
+        };
+        let name = i
+            .checked_sub(1)
+            .and_then(|x| match &tokens[x] {
+                TokenTree::Ident(x) => Some(x.to_string()),
+                _ => None,
+            })
+            .filter(|_| group.delimiter() == Delimiter::Parenthesis);
+        let stream = match name.as_deref() {
+            Some("cfg") => f(group.stream()),
+            Some("cfg_attr") => {
+                let mut stream = group.stream().into_iter();
+                let predicate =
+                    stream.by_ref().take_while(|x| !is_comma(x)).collect();
+                let mut result = f(predicate);
+                if let Some(attrs) = group.stream().into_iter().find(is_comma)
+                {
+                    result.extend(tokens!(attrs,));
+                    result.extend(map_predicates(stream.collect(), f));
+                }
+                result
+            }
+            _ => map_predicates(group.stream(), f),
+        };
+        let mut new_group = Group::new(group.delimiter(), stream);
+        new_group.set_span(group.span());
+        tokens[i] = new_group.into();
+    }
+    tokens.into_iter().collect()
+}
+
+pub(super) fn simplify_attr(attr: TokenStream) -> TokenStream {
+    map_predicates(attr, &mut simplify_predicate)
+}
+
+// Returns the predicate of a "cfg" attribute, if it is valid.
+pub(super) fn attr_predicate(attr: &TokenStream) -> Option<TokenStream> {
+    let tokens: Vec<_> = attr.clone().into_iter().collect();
+    let [TokenTree::Ident(name), TokenTree::Group(predicate)] = &tokens[..]
+    else {
+        return None;
+    };
+    if name.to_string() != "cfg"
+        || predicate.delimiter() != Delimiter::Parenthesis
+    {
+        return None;
+    }
+    match &Predicate::parse_list(predicate.stream())?[..] {
+        [predicate] if predicate.is_valid() => Some(predicate.to_tokens()),
+        _ => None,
+    }
+}
+
+// Returns the negation of a predicate, or [None] if the tokens are not a
+// single valid predicate.
+pub(super) fn negate_predicate(
+    predicate: TokenStream,
+    span: Span,
+) -> Option<TokenStream> {
+    match &Predicate::parse_list(predicate.clone())?[..] {
+        [x] if x.is_valid() => Some(
+            tokens!(
+                Ident::new("not", span),
+                Group::new(Delimiter::Parenthesis, predicate),
+            )
+            .collect(),
+        ),
+        _ => None,
+    }
+}
+
+// Returns the negation of a "cfg" attribute, in the simplified form that
+// would be produced by expanding it.
+pub(super) fn negate_attr(attr: &TokenStream) -> Option<TokenStream> {
+    let tokens: Vec<_> = attr.clone().into_iter().collect();
+    let [TokenTree::Ident(name), TokenTree::Group(predicate)] = &tokens[..]
+    else {
+        return None;
+    };
+    if name.to_string() != "cfg"
+        || predicate.delimiter() != Delimiter::Parenthesis
+    {
+        return None;
+    }
+    let predicate = Group::new(
+        Delimiter::Parenthesis,
+        tokens!(
+            Ident::new("not", predicate.span()),
+            Group::new(Delimiter::Parenthesis, predicate.stream()),
+        )
+        .collect(),
+    );
+    Some(simplify_attr(tokens!(name.clone(), predicate,).collect()))
+}
+
+// Returns the attribute that displays the predicate of a "cfg" attribute in
+// documentation built by docs.rs.
+pub(super) fn doc_attr(attr: &TokenStream) -> Option<TokenStream> {
+    let tokens: Vec<_> = attr.clone().into_iter().collect();
+    let [TokenTree::Ident(name), TokenTree::Group(predicate)] = &tokens[..]
+    else {
+        return None;
+    };
+    if name.to_string() != "cfg"
+        || predicate.delimiter() != Delimiter::Parenthesis
+    {
+        return None;
+    }
+    let span = name.span();
+    Some(
+        tokens!(
+            Ident::new("cfg_attr", span),
+            Group::new(
+                Delimiter::Parenthesis,
+                tokens!(
+                    Ident::new("docsrs", span),
+                    Punct::new(',', Spacing::Alone),
+                    Ident::new("doc", span),
+                    Group::new(
+                        Delimiter::Parenthesis,
+                        tokens!(name.clone(), predicate.clone(),).collect(),
+                    ),
+                )
+                .collect(),
+            ),
+        )
+        .collect(),
+    )
+}
+
+// Returns whether an attribute only affects documentation, including when it
+// is conditional (e.g., "cfg_attr(docsrs, doc(cfg(...)))").
+pub(super) fn is_doc_attr(attr: &TokenStream) -> bool {
+    let tokens: Vec<_> = attr.clone().into_iter().collect();
+    match &tokens[..] {
+        [TokenTree::Ident(name), ..] if name.to_string() == "doc" => true,
+        [TokenTree::Ident(name), TokenTree::Group(args)]
+            if name.to_string() == "cfg_attr"
+                && args.delimiter() == Delimiter::Parenthesis =>
+        {
+            let mut args = args.stream().into_iter().peekable();
+            while args.next_if(|x| !is_comma(x)).is_some() {}
+            let _ = args.next();
+            let mut attrs = false;
+            while args.peek().is_some() {
+                let attr =
+                    args.by_ref().take_while(|x| !is_comma(x)).collect();
+                if !is_doc_attr(&attr) {
+                    return false;
+                }
+                attrs = true;
+            }
+            attrs
+        }
+        _ => false,
+    }
+}
+
+// Returns the attribute that displays text from the "doc-text" directive in
+// documentation built by docs.rs. Blank lines keep it from joining the
+// paragraphs of adjacent documentation attributes.
+pub(super) fn doc_text_attr(text: &str, span: Span) -> TokenStream {
+    let mut text = Literal::string(&format!("\n\n{}\n\n", text));
+    text.set_span(span);
+    tokens!(
+        Ident::new("cfg_attr", span),
+        Group::new(
+            Delimiter::Parenthesis,
+            tokens!(
+                Ident::new("docsrs", span),
+                Punct::new(',', Spacing::Alone),
+                Ident::new("doc", span),
+                Punct::new('=', Spacing::Alone),
+                text,
+            )
+            .collect(),
+        ),
+    )
+    .collect()
+}
+
+// Custom names can be declared in the manifest using the "check-cfg"
+// configuration of the "unexpected_cfgs" lint.
+fn declared_names() -> Vec<String> {
+    let Some(manifest) = env::var_os("CARGO_MANIFEST_DIR").and_then(|x| {
+        fs::read_to_string(Path::new(&x).join("Cargo.toml")).ok()
+    }) else {
+        return Vec::new();
+    };
+
+    names::declared_names(&manifest)
+}
+
+fn predicate_options(predicate: TokenStream) -> Vec<(Ident, Option<Literal>)> {
+    let mut options = Vec::new();
+    for predicate in Predicate::parse_list(predicate).into_iter().flatten() {
+        predicate.options(&mut options);
+    }
+    options
+}
+
+// Returns the options that must be set for a predicate to be true, or the
+// part of it that cannot be satisfied by setting options.
+pub(super) fn required_options(
+    predicate: TokenStream,
+) -> Result<Vec<(Ident, Option<Literal>)>, TokenStream> {
+    let predicates = Predicate::parse_list(predicate.clone());
+    let [predicate] = predicates.as_deref().unwrap_or_default() else {
+        return Err(predicate);
+    };
+    let mut options = Vec::new();
+    predicate
+        .required_options(&mut options)
+        .map_err(Predicate::to_tokens)?;
+    Ok(options)
+}
+
+pub(super) fn predicate_names(predicate: TokenStream) -> Vec<Ident> {
+    predicate_options(predicate)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+// Returns the options used by predicates of "cfg" and "cfg_attr" attributes,
+// with their values, if any.
+pub(super) fn attr_options(
+    attr: &TokenStream,
+) -> Vec<(Ident, Option<Literal>)> {
+    let mut options = Vec::new();
+    let _ = map_predicates(attr.clone(), &mut |predicate| {
+        options.extend(predicate_options(predicate.clone()));
+        predicate
+    });
+    options
+}
+
+// Returns the names used by predicates of "cfg" and "cfg_attr" attributes.
+pub(super) fn attr_names(attr: &TokenStream) -> Vec<Ident> {
+    attr_options(attr)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+// Returns the names used by predicates that are not known to the compiler,
+// each with the most similar known name, if any.
+pub(super) fn unknown_names(
+    attr: &TokenStream,
+) -> Vec<(Ident, Option<String>)> {
+    let mut names = attr_names(attr);
+    names.retain(|x| names::is_custom_name(&x.to_string()));
+    if names.is_empty() {
+        return Vec::new();
+    }
+
+    let declared_names = declared_names();
+    names
+        .into_iter()
+        .filter(|x| !declared_names.contains(&x.to_string()))
+        .map(|name| {
+            let name_string = name.to_string();
+            let similar_name = names::WELL_KNOWN_NAMES
+                .iter()
+                .copied()
+                .chain(declared_names.iter().map(String::as_str))
+                .map(|x| (names::distance(&name_string, x), x))
+                .filter(|&(distance, _)| {
+                    distance <= (name_string.len() / 3).max(1)
+                })
+                .min()
+                .map(|(_, x)| x.to_owned());
+            (name, similar_name)
+        })
+        .collect()
+}
@@ -0,0 +1,54 @@
+// Codes are stable identifiers, so they must never be reassigned or reused
+// after being published.
+macro_rules! codes {
+    ( $( $variant:ident => $id:literal , )+ ) => {
+        #[derive(Clone, Copy)]
+        pub(super) enum Code {
+            $( $variant, )+
+        }
+
+        impl Code {
+            pub(super) fn parse(id: &str) -> Option<Self> {
+                match id {
+                    $( $id => Some(Self::$variant), )+
+                    _ => None,
+                }
+            }
+
+            pub(super) const fn id(self) -> &'static str {
+                match self {
+                    $( Self::$variant => $id, )+
+                }
+            }
+
+            pub(super) const fn explanation(self) -> &'static str {
+                match self {
+                    $(
+                        Self::$variant => {
+                            include_str!(concat!("codes/", $id, ".md"))
+                        }
+                    )+
+                }
+            }
+        }
+    };
+}
+
+codes! {
+    Syntax => "AA0001",
+    Option => "AA0002",
+    UnknownAlias => "AA0003",
+    Level => "AA0004",
+    Wildcards => "AA0005",
+    InvalidAttribute => "AA0006",
+    Reading => "AA0007",
+    Parsing => "AA0008",
+    Changed => "AA0009",
+    Unnecessary => "AA0010",
+    Shadowing => "AA0011",
+    UnexpectedCfg => "AA0012",
+    Conflict => "AA0013",
+    Module => "AA0014",
+    DisallowedCfg => "AA0015",
+    UnsettableCfg => "AA0016",
+}
@@ -0,0 +1,104 @@
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use proc_macro::Span;
+
+use super::Code;
+
+const FILE: &str = "attr_alias.jsonl";
+
+pub(super) fn escape(string: &str) -> String {
+    let mut result = "\"".to_owned();
+    for ch in string.chars() {
+        match ch {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            _ if ch.is_control() => {
+                result += &format!("\\u{:04x}", u32::from(ch));
+            }
+            _ => result.push(ch),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(attr_alias_span_file)]
+fn location(span: Span) -> String {
+    format!(
+        "\"file\":{},\"line\":{},\"column\":{}",
+        escape(&span.file()),
+        span.line(),
+        span.column(),
+    )
+}
+
+#[cfg(not(attr_alias_span_file))]
+fn location(_: Span) -> String {
+    "\"file\":null,\"line\":null,\"column\":null".to_owned()
+}
+
+// Diagnostics are additionally written as JSON lines when requested, for use
+// by other tools. The file is replaced by the first line of each
+// compilation, so it only contains diagnostics for the latest one. Failing
+// to write them should not affect the build.
+pub(super) fn emit(
+    level: &str,
+    code: Code,
+    span: Span,
+    message: &str,
+    notes: &[(Option<Span>, String)],
+) {
+    static WRITTEN: Mutex<bool> = Mutex::new(false);
+
+    let Some(output) = env::var_os("ATTR_ALIAS_JSON") else {
+        return;
+    };
+
+    let notes: Vec<_> = notes
+        .iter()
+        .map(|(span, note)| {
+            format!(
+                "{{\"message\":{}{}}}",
+                escape(note),
+                span.map(|x| format!(",{}", location(x)))
+                    .unwrap_or_default(),
+            )
+        })
+        .collect();
+    let line = format!(
+        "{{\"level\":{},\"code\":{},\"message\":{},{},\"notes\":[{}]}}\n",
+        escape(level),
+        escape(code.id()),
+        escape(message),
+        location(span),
+        notes.join(","),
+    );
+
+    // Cargo interprets lines on the standard error stream that begin with "{"
+    // as its own messages, so a prefix is added.
+    if output == "stderr" {
+        eprint!("attr_alias: {}", line);
+    } else if output == "out_dir" {
+        if let Some(out_dir) = env::var_os("OUT_DIR") {
+            let mut written =
+                WRITTEN.lock().unwrap_or_else(PoisonError::into_inner);
+            let _ = OpenOptions::new()
+                .append(*written)
+                .write(true)
+                .truncate(!*written)
+                .create(true)
+                .open(Path::new(&out_dir).join(FILE))
+                .and_then(|mut file| file.write_all(line.as_bytes()));
+            *written = true;
+        }
+    }
+}
@@ -0,0 +1,289 @@
+// The compiler's tokenizer is only available to procedural macros, so values
+// are tokenized here for the command-line checker. Only the distinctions
+// needed by the checks are kept, and each token records its offset within the
+// alias file. The macros also use it to validate values, since the compiler
+// aborts them for some invalid text, such as unterminated literals.
+
+// The macros only check that values can be tokenized, so the contents of
+// tokens are only read by the command-line checker.
+#[allow(dead_code)]
+pub(super) enum Kind {
+    Group(char, Vec<Token>),
+    Ident(String),
+    Punct(char),
+    Literal,
+}
+
+#[allow(dead_code)]
+pub(super) struct Token {
+    pub(super) kind: Kind,
+    pub(super) offset: usize,
+}
+
+#[allow(dead_code)]
+impl Token {
+    pub(super) fn is_ident(&self, name: &str) -> bool {
+        matches!(&self.kind, Kind::Ident(x) if x == name)
+    }
+
+    pub(super) fn is_punct(&self, ch: char) -> bool {
+        matches!(self.kind, Kind::Punct(x) if x == ch)
+    }
+
+    pub(super) fn group(&self, delimiter: char) -> Option<&[Self]> {
+        match &self.kind {
+            Kind::Group(x, tokens) if *x == delimiter => Some(tokens),
+            _ => None,
+        }
+    }
+}
+
+pub(super) struct Error {
+    pub(super) offset: usize,
+    pub(super) message: String,
+}
+
+// These keywords have meanings that cannot be escaped.
+const RESERVED_NAMES: &[&str] = &["_", "crate", "self", "super", "Self"];
+
+fn is_ident_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+fn is_ident_continue(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl Lexer<'_> {
+    fn rest(&self) -> &str {
+        &self.source[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, offset: usize, message: &str) -> Error {
+        Error {
+            offset,
+            message: message.to_owned(),
+        }
+    }
+
+    fn skip_while<F>(&mut self, mut f: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        let rest = self.rest();
+        self.offset += rest.find(|x| !f(x)).unwrap_or(rest.len());
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), Error> {
+        loop {
+            self.skip_while(char::is_whitespace);
+            if self.rest().starts_with("//") {
+                self.skip_while(|x| x != '\n');
+            } else if self.rest().starts_with("/*") {
+                let start = self.offset;
+                let mut depth = 0_usize;
+                loop {
+                    let rest = self.rest();
+                    if rest.starts_with("/*") {
+                        depth += 1;
+                        self.offset += 2;
+                    } else if rest.starts_with("*/") {
+                        depth -= 1;
+                        self.offset += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else if let Some(ch) = rest.chars().next() {
+                        self.offset += ch.len_utf8();
+                    } else {
+                        return Err(
+                            self.error(start, "unterminated block comment")
+                        );
+                    }
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    // Escapes are skipped without being validated.
+    fn quoted(&mut self, quote: char) -> Result<(), Error> {
+        let start = self.offset;
+        self.offset += 1;
+        let mut escaped = false;
+        while let Some(ch) = self.peek() {
+            self.offset += ch.len_utf8();
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == quote {
+                self.skip_while(is_ident_continue);
+                return Ok(());
+            }
+        }
+        Err(self.error(start, "unterminated literal"))
+    }
+
+    fn raw_string(&mut self) -> Result<(), Error> {
+        let start = self.offset;
+        self.skip_while(|x| x != '#' && x != '"');
+        let hashes =
+            self.rest().len() - self.rest().trim_start_matches('#').len();
+        self.offset += hashes + 1;
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let Some(end) = self.rest().find(&terminator) else {
+            return Err(self.error(start, "unterminated raw string literal"));
+        };
+        self.offset += end + terminator.len();
+        self.skip_while(is_ident_continue);
+        Ok(())
+    }
+
+    fn token(&mut self) -> Result<Option<Token>, Error> {
+        let offset = self.offset;
+        let rest = self.rest();
+        let Some(ch) = rest.chars().next() else {
+            return Ok(None);
+        };
+        let kind = if ["r\"", "r#\"", "r##", "br\"", "br#", "cr\"", "cr#"]
+            .iter()
+            .any(|x| rest.starts_with(x))
+        {
+            self.raw_string()?;
+            Kind::Literal
+        } else if ["b\"", "b'", "c\""].iter().any(|x| rest.starts_with(x)) {
+            self.offset += 1;
+            self.quoted(self.peek().expect("missing quote"))?;
+            Kind::Literal
+        } else if ch == '"' {
+            self.quoted(ch)?;
+            Kind::Literal
+        } else if ch == '\'' {
+            // Lifetimes are parsed as punctuation followed by an identifier.
+            let mut chars = rest.chars().skip(1);
+            if chars.next().is_some_and(is_ident_start)
+                && chars.next() != Some('\'')
+            {
+                self.offset += 1;
+                Kind::Punct(ch)
+            } else {
+                self.quoted(ch)?;
+                Kind::Literal
+            }
+        } else if ch.is_ascii_digit() {
+            self.skip_while(is_ident_continue);
+            if self.rest().starts_with('.')
+                && self.rest()[1..].starts_with(|x: char| x.is_ascii_digit())
+            {
+                self.offset += 1;
+                self.skip_while(is_ident_continue);
+            }
+            Kind::Literal
+        } else if is_ident_start(ch) {
+            let raw = rest.starts_with("r#");
+            if raw {
+                self.offset += 2;
+            }
+            let start = self.offset;
+            self.skip_while(is_ident_continue);
+            if raw {
+                let name = &self.source[start..self.offset];
+                if name.is_empty() || !name.starts_with(is_ident_start) {
+                    return Err(self
+                        .error(offset, "expected an identifier after `r#`"));
+                }
+                if RESERVED_NAMES.contains(&name) {
+                    return Err(self.error(
+                        offset,
+                        &format!("`{}` cannot be a raw identifier", name),
+                    ));
+                }
+            }
+            Kind::Ident(self.source[offset..self.offset].to_owned())
+        } else if let Some(close) = match ch {
+            '(' => Some(')'),
+            '[' => Some(']'),
+            '{' => Some('}'),
+            _ => None,
+        } {
+            self.offset += 1;
+            let tokens = self.tokens(Some(close))?;
+            let Some(next) = self.peek() else {
+                return Err(self.error(offset, "unclosed delimiter"));
+            };
+            if next != close {
+                return Err(self.error(
+                    self.offset,
+                    &format!("mismatched closing delimiter `{}`", next),
+                ));
+            }
+            self.offset += 1;
+            Kind::Group(ch, tokens)
+        } else if ch.is_ascii_punctuation() {
+            self.offset += 1;
+            Kind::Punct(ch)
+        } else {
+            return Err(
+                self.error(offset, &format!("unknown start of token: {}", ch))
+            );
+        };
+        Ok(Some(Token { kind, offset }))
+    }
+
+    fn tokens(&mut self, close: Option<char>) -> Result<Vec<Token>, Error> {
+        let mut tokens = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(')' | ']' | '}') if close.is_some() => return Ok(tokens),
+                Some(ch @ (')' | ']' | '}')) => {
+                    return Err(self.error(
+                        self.offset,
+                        &format!("unexpected closing delimiter `{}`", ch),
+                    ));
+                }
+                _ => {}
+            }
+            match self.token()? {
+                Some(token) => tokens.push(token),
+                None => return Ok(tokens),
+            }
+        }
+    }
+}
+
+// Tokenizes the given range of the alias file.
+pub(super) fn tokenize(
+    source: &str,
+    start: usize,
+    end: usize,
+) -> Result<Vec<Token>, Error> {
+    Lexer {
+        source: &source[..end],
+        offset: start,
+    }
+    .tokens(None)
+}
+
+// Returns the offset of the first token at or after the given offset. Only
+// the command-line checker needs it.
+#[allow(dead_code)]
+pub(super) fn skip_trivia(
+    source: &str,
+    offset: usize,
+) -> Result<usize, Error> {
+    let mut lexer = Lexer { source, offset };
+    lexer.skip_trivia()?;
+    Ok(lexer.offset)
+}
@@ -8,16 +8,128 @@
 //!
 //! Due to how procedural macros work and to avoid redundancy, this crate will
 //! always read aliases from
-#![doc = concat!("\"", alias_file!(), "\".")]
+#![doc = concat!("\"", alias_file!(), "\",")]
+//! relative to the directory containing the "Cargo.toml" file of the package
+//! being compiled. Therefore, aliases are also available in doctests,
+//! examples, and members of workspaces.
+//!
+//! The file is only read, so packages can also be built from registries and
+//! vendored directories, whose sources are read-only. It is included when
+//! packages are published, unless the `include` or `exclude` fields of
+//! "Cargo.toml" omit it.
+//!
+//! The macros can also be used by build scripts, when this crate is added as
+//! a build dependency. The same file is read for them, but configuration
+//! options within build scripts describe the host instead of the target. The
+//! target is described by the `CARGO_CFG_*` environment variables, which
+//! Cargo sets when running build scripts.
+//!
 //! Other files may be supported in future versions, but doing so is not
 //! currently possible. Open an issue if this is important for your build.
 //!
 //! ## Syntax
 //!
 //! - Each alias must begin with `*` and be assigned to a valid attribute
-//!   value.
+//!   value. Errors in the file will be reported with the line and column
+//!   where they occur. Values are only parsed when their aliases are first
+//!   used, so errors in them will not be reported until then.
 //! - Aliases can reference others, but referenced aliases must be listed
 //!   first.
+//! - Aliases should not be named after built-in configuration options,
+//!   attributes, or keywords, such as `test`, `inline`, or `crate`, since uses
+//!   of them would be confusing. A warning will be reported for those names.
+//! - The "default" alias is a pattern, so references can be nested anywhere
+//!   within its value (e.g., `cfg_attr(*, doc(cfg(attr_alias(docs))))`).
+//! - Values containing `;` are bundles of complete attributes, separated by
+//!   that character. The default pattern is not applied to bundles, and an
+//!   explicit pattern will be applied to each attribute in them.
+//! - A value ending with `;` is a bundle containing a single attribute. It
+//!   allows an alias to stand in for an entire attribute, including its name
+//!   (e.g., `tokio::main` or `serde(rename_all = "camelCase")`). No other
+//!   attribute of a bundle can be empty.
+//! - Predicates of expanded `cfg` and `cfg_attr` attributes are simplified
+//!   after substitution. Nested `all` and `any` operators of the same kind
+//!   are flattened, duplicate operands are removed, and double negation is
+//!   cancelled (e.g., `any(any(unix, windows), unix)` becomes
+//!   `any(unix, windows)`).
+//! - Lines beginning with `#!` are directives, which are usually followed by
+//!   a comma-separated list of names. The supported directives are:
+//!   - `#!allow-cfg` restricts configuration names (e.g.,
+//!     `#!allow-cfg target_os, feature, docsrs`). When it is present, an
+//!     error will be reported for any other name used by `cfg` and
+//!     `cfg_attr` attributes in alias values or expansions. The listed names
+//!     are not checked by `#!check-cfg-names`.
+//!   - `#!allow-lints` adds `allow` attributes for all lints to the items
+//!     emitted by macros for their own use, such as the trigger described in
+//!     [Reproducibility] and the items defined by [`alias_consts!`] and
+//!     [`alias_macros!`]. Crates denying lints, including those of Clippy,
+//!     will then not need to allow them for these items. Warnings reported
+//!     by macros are not affected.
+//!   - `#!check-cfg-names` checks the configuration names used by expanded
+//!     predicates against the [well-known names] and those declared by the
+//!     `check-cfg` configuration of the `unexpected_cfgs` lint in
+//!     "Cargo.toml". A warning will be reported for other names, which are
+//!     likely to be typos (e.g., `target_oss`). Names declared by build
+//!     scripts, including by [`check_cfg!`], cannot be seen by macros, so
+//!     this directive should not be used with them. The compiler will still
+//!     report unexpected names in expansions.
+//!   - `#!doc-cfg` documents where items are available. Each `cfg`
+//!     attribute expanded by a marker without an explicit pattern will be
+//!     followed by `cfg_attr(docsrs, doc(cfg(...)))` with the same
+//!     predicate, unless the expansion already includes it. When aliases are
+//!     listed, only their markers will be affected. The `docsrs` option is
+//!     set by docs.rs, which also requires
+//!     `#![cfg_attr(docsrs, feature(doc_cfg))]`. This directive is not needed
+//!     when `doc_auto_cfg` is enabled instead.
+//!   - `#!doc-text` gives an alias a description of where items are
+//!     available, which is used by `#!doc-cfg` instead of the predicate
+//!     (e.g., `#!doc-text tokio_net Available on crate feature tokio-net
+//!     only.`). It is followed by the name of the alias and the text, which
+//!     continues until the end of the line. Markers using the alias will be
+//!     followed by `cfg_attr(docsrs, doc = "...")`, so the text will be added
+//!     to the documentation as a separate paragraph where the marker is
+//!     written. Markers written after documentation comments will add it as
+//!     the last paragraph.
+//!   - `#!feature-aliases` defines an alias for each feature declared by the
+//!     `[features]` table of "Cargo.toml". Each is named by its feature with
+//!     a `feat_` prefix, replacing `-` with `_` (e.g.,
+//!     `*feat_tokio_net = feature = "tokio-net"`). The aliases are defined
+//!     where the directive is written, so later aliases can reference them,
+//!     and an alias already defined with the same name is kept instead. The
+//!     manifest is not parsed as TOML, so features must be declared on
+//!     separate lines.
+//!   - `#!generate-not` defines a negation prefixed by `not_` for each alias
+//!     written before it whose value is a predicate (e.g.,
+//!     `*not_unix_like = not(unix)` for `*unix_like = unix`). Values that
+//!     reference other aliases are only resolved when they are used, so
+//!     their aliases are skipped, along with those already prefixed by
+//!     `not_`. These aliases are defined in the same way as by
+//!     `#!feature-aliases`. Markers can also negate an alias directly, which
+//!     is described by [`#[attr_alias]`][macro@attr_alias].
+//!   - `#!msrv-aliases` defines `*msrv` as the `rust-version` of the
+//!     package as a string literal, which can be used by patterns (e.g.,
+//!     `#[attr_alias(msrv, doc = concat!("Requires Rust ", *, "."))]`). It
+//!     can be followed by a list of versions, and an alias named by each with
+//!     a `since_` prefix will be defined as a predicate that is true when the
+//!     minimum supported version is at least that version (e.g.,
+//!     `#!msrv-aliases 1.80` defines `since_1_80`). Code using newer features
+//!     can then be enabled by increasing `rust-version`. These aliases are
+//!     defined in the same way as by `#!feature-aliases`.
+//!   - `#!platforms` declares the supported operating systems (e.g.,
+//!     `#!platforms macos, windows, linux`). An alias named by each will be
+//!     defined as its `target_os` option, along with a negation prefixed by
+//!     `not_` (e.g., `*not_macos = not(target_os = "macos")`) and
+//!     `*any_supported` combining all of them. These aliases are defined in
+//!     the same way as by `#!feature-aliases`.
+//!   - `#!trigger-attr` adds an attribute to the trigger described in
+//!     [Reproducibility] (e.g., `#!trigger-attr doc(hidden)`). It is
+//!     followed by the attribute, which continues until the end of the line,
+//!     and it can be repeated.
+//!   - `#!trigger-prefix` names the trigger, instead of leaving it anonymous
+//!     (e.g., `#!trigger-prefix __ATTR_ALIAS_TRIGGER_`). A hash of the
+//!     location of each invocation is appended to the prefix, so that each
+//!     name is unique and does not change when other invocations are added,
+//!     and `allow(dead_code)` is added to its attributes.
 //!
 //! ## Example
 //!
@@ -25,17 +137,110 @@
 #![doc = include_str!(concat!("../", alias_file!()))]
 //! ```
 //!
+//! # Reproducibility
+//!
+//! Expansions only depend on the tokens passed to each macro and the contents
+//! of the [alias file]. Attributes are always emitted in the order they are
+//! written, including those from bundles. The only exception is the trigger
+//! used to rebuild when the alias file changes, which includes the absolute
+//! path of that file. Its separators are normalized on Windows, but it will
+//! otherwise differ between build directories. The trigger is not emitted
+//! when the compiler can track the file directly, which is currently only
+//! possible with the **nightly** feature. Otherwise, it is an anonymous
+//! constant, which can be decorated using the `#!trigger-attr` and
+//! `#!trigger-prefix` directives.
+//!
+//! All invocations within a single compilation observe the same aliases. If
+//! the [alias file] changes while a crate is being compiled, an error will be
+//! reported, and building again will use the new contents. Long-running
+//! processes that expand macros repeatedly, such as expansion tests, can set
+//! the `ATTR_ALIAS_REFRESH` environment variable to `1` to use the new
+//! contents instead.
+//!
+//! # Debugging
+//!
+//! Each error and warning has a code, such as `AA0003`, which is shown at the
+//! beginning of its message. The [`explain!`] macro can be used to view more
+//! information about a code.
+//!
+//! Procedural macros cannot emit warnings without the **nightly** feature, so
+//! they are otherwise emitted as uses of deprecated constants named by their
+//! codes. The compiler will report them using the `deprecated` lint, followed
+//! by the message (e.g., ``use of deprecated constant `_::AA0012`: ...``).
+//!
+//! Spans cannot refer to locations in the alias file, so diagnostics about an
+//! alias include a note with the line and column of its definition (e.g.,
+//! `alias 'macos' is defined at src/attr-aliases.txt:2:2`). Most editors and
+//! terminals allow jumping to these locations.
+//!
+//! When the `ATTR_ALIAS_DEBUG` environment variable is set to `1`, each
+//! resolution will be printed to the standard error stream, including the
+//! aliases and their definitions, the pattern, the expanded attributes, and
+//! the location of the invocation. Changing the variable does not cause
+//! crates to be rebuilt, so a clean build may be necessary to see all output.
+//!
+//! Errors and warnings can also be reported as JSON lines for use by other
+//! tools, by setting the `ATTR_ALIAS_JSON` environment variable. Each line is
+//! an object with "level", "code", "message", "file", "line", "column", and
+//! "notes" fields. The following values are supported:
+//! - `stderr` -
+//!   Print each line to the standard error stream, prefixed with
+//!   "attr\_alias: ", since Cargo interprets lines beginning with `{` as its
+//!   own messages.
+//! - `out_dir` -
+//!   Write each line to "attr\_alias.jsonl" in the directory given by the
+//!   `OUT_DIR` environment variable. The file is replaced by each
+//!   compilation that reports a diagnostic. Cargo only sets that variable for
+//!   packages with a build script.
+//!
+//! Messages are the same on every platform and only include absolute paths
+//! in notes about reading files, so tools can compare them between builds.
+//!
+//! When the `ATTR_ALIAS_USAGE` environment variable is set to `1`, a report of
+//! how often each alias is used will be written to "attr\_alias\_usage.jsonl"
+//! in the directory given by `OUT_DIR`. Each line is an object with "alias",
+//! "count", and "files" fields, and unused aliases have a count of zero. Uses
+//! by other aliases within the [alias file] are not counted, but the default
+//! alias is counted whenever its pattern is applied.
+//!
+//! When the `ATTR_ALIAS_SNAPSHOT` environment variable is set to `1`, the
+//! item expanded by each invocation of [`#[eval]`][macro@eval] and
+//! [`eval_block!`] will be written to the "attr\_alias\_snapshots" directory
+//! in the directory given by `OUT_DIR`. Files are numbered in the order of
+//! expansion, and each begins with a comment giving the location of its
+//! invocation. Attributes and statements are written on separate lines, so
+//! the directory can be compared between commits without tools such as
+//! cargo-expand. It is replaced whenever the crate is compiled, including for
+//! tests.
+//!
 //! # Features
 //!
 //! These features are optional and can be enabled or disabled in a
 //! "Cargo.toml" file.
 //!
+//! ### Optional Features
+//!
+//! - **cli** -
+//!   Provides the "cargo-attr-alias" binary, which checks the [alias file] of
+//!   a package without compiling it. It can be installed using
+//!   `cargo install attr_alias --features cli` and run as
+//!   `cargo attr-alias [PACKAGE_DIR]`. Errors are reported for problems that
+//!   the macros would only report when an alias is used, and warnings are
+//!   also reported for aliases that are never used by the package.
+//!
+//! - **testing** -
+//!   Provides [`assert_expansion!`], which checks expansions using an inline
+//!   table of aliases. It is intended for unit tests of crates that use
+//!   aliases, such as for their configuration logic.
+//!
 //! ### Nightly Features
 //!
 //! These features are unstable, since they rely on unstable Rust features.
 //!
 //! - **nightly** -
-//!   Provides [`#[attr_alias]`][macro@attr_alias].
+//!   Provides [`#[attr_alias]`][macro@attr_alias]. Errors will also be
+//!   reported as native diagnostics, with notes pointing to related spans,
+//!   and the [alias file] will be tracked without emitting a trigger.
 //!
 //! # Dependencies
 //!
@@ -88,27 +293,42 @@
 //!
 //! </details></li></ul>
 //!
+//! [alias file]: #alias-file
 //! [cfg\_aliases]: https://crates.io/crates/cfg_aliases
 //! [macro\_rules\_attribute]: https://crates.io/crates/macro_rules_attribute
 //! [proc\_macro2]: https://crates.io/crates/proc_macro2
 //! [quote]: https://crates.io/crates/quote
+//! [Reproducibility]: #reproducibility
 //! [syn]: https://crates.io/crates/syn
+//! [well-known names]: https://doc.rust-lang.org/rustc/check-cfg.html#well-known-names-and-values
 
 // Only require a nightly compiler when building documentation for docs.rs.
 // This is a private option that should not be used.
 // https://github.com/rust-lang/docs.rs/issues/147#issuecomment-389544407
 #![cfg_attr(feature = "nightly", feature(doc_cfg))]
-#![cfg_attr(feature = "nightly", feature(track_path))]
+#![cfg_attr(feature = "nightly", feature(proc_macro_diagnostic))]
+#![cfg_attr(feature = "nightly", feature(proc_macro_span))]
+#![cfg_attr(feature = "nightly", feature(proc_macro_tracked_path))]
 #![forbid(unsafe_code)]
 #![warn(unused_results)]
 
 use std::error;
+use std::iter;
+use std::mem;
 use std::result;
+#[cfg(feature = "testing")]
+use std::str::FromStr;
+use std::sync::Arc;
 
 #[cfg(feature = "nightly")]
-use proc_macro::tracked_path;
+use proc_macro::tracked;
 use proc_macro::Delimiter;
+#[cfg(feature = "nightly")]
+use proc_macro::Diagnostic;
 use proc_macro::Group;
+use proc_macro::Ident;
+#[cfg(feature = "nightly")]
+use proc_macro::Level;
 use proc_macro::Literal;
 use proc_macro::Punct;
 use proc_macro::Spacing;
@@ -131,6 +351,19 @@ macro_rules! tokens {
     }};
 }
 
+macro_rules! next {
+    ( $item:expr , $type:ident $(, $method:ident => $value:expr)? $(,)? ) => {
+        if let Some(token) = $item.next() {
+            match token {
+                TokenTree::$type(x) $(if x.$method() == $value)? => Ok(x),
+                _ => Err(Error::token(&token)),
+            }
+        } else {
+            Err(Error::new(Code::Syntax, "unexpected end of tokens"))
+        }
+    }
+}
+
 macro_rules! path {
     ( $($name:expr),+ ) => {{
         use proc_macro::Ident;
@@ -151,55 +384,531 @@ macro_rules! path {
 mod aliases;
 use aliases::Aliases;
 
-fn core_macro(name: &str, arg: &str) -> impl Iterator<Item = TokenTree> {
+mod cfg;
+
+mod codes;
+use codes::Code;
+
+mod json;
+
+mod lexer;
+
+mod names;
+
+mod options;
+use options::Options;
+
+#[cfg_attr(attr_alias_tracked_path, allow(dead_code))]
+mod path;
+
+mod snapshot;
+
+mod syntax;
+
+mod token;
+
+mod usage;
+
+mod warning;
+
+fn core_macro(name: &str, arg: Literal) -> impl Iterator<Item = TokenTree> {
     path!("core", name).chain(tokens!(
         Punct::new('!', Spacing::Alone),
-        Group::new(
-            Delimiter::Parenthesis,
-            TokenTree::Literal(Literal::string(arg)).into(),
-        ),
+        Group::new(Delimiter::Parenthesis, TokenTree::Literal(arg).into()),
         Punct::new(';', Spacing::Alone),
     ))
 }
 
+// Build scripts using the alias file must run again when it changes, and
+// they must also be rebuilt, since their output is expanded from it.
+fn build_script_output<I>(aliases: &Aliases, lines: I) -> Result<TokenStream>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut result: TokenStream = aliases.create_trigger()?.collect();
+    let lines =
+        iter::once(format!("cargo:rerun-if-changed={}", Aliases::FILE,))
+            .chain(lines);
+    for line in lines {
+        result.extend(path!("std", "println").chain(tokens!(
+            Punct::new('!', Spacing::Alone),
+            Group::new(
+                Delimiter::Parenthesis,
+                tokens!(
+                    Literal::string("{}"),
+                    Punct::new(',', Spacing::Alone),
+                    Literal::string(&line),
+                )
+                .collect(),
+            ),
+            Punct::new(';', Spacing::Alone),
+        )));
+    }
+    Ok(result)
+}
+
 struct Error {
+    code: Code,
     span: Span,
     message: String,
+    notes: Vec<(Option<Span>, String)>,
+    // Errors can span multiple tokens by also specifying the span of the last.
+    end_span: Option<Span>,
 }
 
 impl Error {
-    fn new(message: &'static str) -> Self {
+    fn new(code: Code, message: &'static str) -> Self {
         Self {
+            code,
             span: Span::call_site(),
             message: message.to_owned(),
+            notes: Vec::new(),
+            end_span: None,
         }
     }
 
-    fn new_from<T>(error: T, message: &'static str) -> Self
+    fn new_from<T>(code: Code, error: T, message: &'static str) -> Self
     where
         T: error::Error,
     {
         Self {
+            code,
             span: Span::call_site(),
             message: format!("error {}: {}", message, error),
+            notes: Vec::new(),
+            end_span: None,
         }
     }
 
-    fn token(token: &TokenTree) -> Self {
+    fn spanned(code: Code, span: Span, message: String) -> Self {
         Self {
-            span: token.span(),
-            message: "unexpected token".to_owned(),
+            code,
+            span,
+            message,
+            notes: Vec::new(),
+            end_span: None,
+        }
+    }
+
+    fn token(token: &TokenTree) -> Self {
+        Self::spanned(
+            Code::Syntax,
+            token.span(),
+            "unexpected token".to_owned(),
+        )
+    }
+
+    // Resolution errors are reported for the entire attribute, so the region
+    // highlighted by editors is not limited to a single token.
+    fn spanning(mut self, span: Span, end_span: Span) -> Self {
+        self.span = span;
+        self.end_span = Some(end_span);
+        self
+    }
+
+    fn note(mut self, span: Option<Span>, note: String) -> Self {
+        self.notes.push((span, note));
+        self
+    }
+
+    // Displayed diagnostics include the code and explain how to find more
+    // information about it.
+    fn explained(mut self) -> Self {
+        let code = self.code.id();
+        self.message = format!("[{}] {}", code, self.message);
+        self.note(
+            None,
+            format!(
+                "for more information, see `attr_alias::explain!({})`",
+                code,
+            ),
+        )
+    }
+
+    fn emit_json(&self, level: &str) {
+        json::emit(level, self.code, self.span, &self.message, &self.notes);
+    }
+
+    #[cfg(feature = "nightly")]
+    fn emit(self, level: Level) {
+        let error = self.explained();
+        let span = error
+            .end_span
+            .and_then(|x| error.span.join(x))
+            .unwrap_or(error.span);
+        let mut diagnostic = Diagnostic::spanned(span, level, error.message);
+        for (span, note) in error.notes {
+            diagnostic = match span {
+                Some(span) => diagnostic.span_note(span, note),
+                None => diagnostic.note(note),
+            };
+        }
+        diagnostic.emit();
+    }
+
+    // Warnings are always included in JSON output.
+    fn warn(self) {
+        self.emit_json("warning");
+
+        #[cfg(feature = "nightly")]
+        self.emit(Level::Warning);
+        #[cfg(not(feature = "nightly"))]
+        {
+            let code = self.code.id();
+            let span = self.span;
+            warning::push(code, self.into_message(), span);
+        }
+    }
+
+    // Diagnostics emitted as tokens can only have a message, so the notes
+    // are appended to it.
+    #[cfg(not(feature = "nightly"))]
+    fn into_message(self) -> String {
+        let error = self.explained();
+        let mut message = error.message;
+        for (_, note) in error.notes {
+            message += "\n  = note: ";
+            message += &note;
         }
+        message
     }
 
+    // Nightly compilers can report notes with their own spans, but the error
+    // is then emitted directly, so no tokens are returned.
+    #[cfg(feature = "nightly")]
     fn into_compile_error(self) -> TokenStream {
-        core_macro("compile_error", &self.message)
-            .map(|mut token| {
-                token.set_span(self.span);
-                token
-            })
-            .collect()
+        self.emit_json("error");
+
+        self.emit(Level::Error);
+        TokenStream::new()
+    }
+
+    #[cfg(not(feature = "nightly"))]
+    fn into_compile_error(self) -> TokenStream {
+        self.emit_json("error");
+        // Warnings are not needed when compilation fails.
+        warning::clear();
+
+        let span = self.span;
+        let end_span = self.end_span;
+        let message = self.into_message();
+        // The closing tokens are given the end span, which makes the compiler
+        // report the error for the region between the spans.
+        let mut tokens: Vec<_> =
+            core_macro("compile_error", Literal::string(&message)).collect();
+        let len = tokens.len();
+        for (i, token) in tokens.iter_mut().enumerate() {
+            token.set_span(if i + 2 < len {
+                span
+            } else {
+                end_span.unwrap_or(span)
+            });
+        }
+        tokens.into_iter().collect()
+    }
+}
+
+// Exported files are written by the build script to its output directory,
+// which is within the target directory.
+fn export(
+    aliases: &Aliases,
+    file: &str,
+    contents: &str,
+) -> Result<TokenStream> {
+    fn method(name: &str, arg: Literal) -> impl Iterator<Item = TokenTree> {
+        tokens!(
+            Punct::new('.', Spacing::Alone),
+            Ident::new(name, Span::call_site()),
+            Group::new(Delimiter::Parenthesis, tokens!(arg,).collect()),
+        )
+    }
+
+    let out_dir: TokenStream = path!("std", "env", "var_os")
+        .chain(tokens!(Group::new(
+            Delimiter::Parenthesis,
+            tokens!(Literal::string("OUT_DIR"),).collect(),
+        ),))
+        .chain(method("expect", Literal::string("missing OUT_DIR")))
+        .collect();
+    let path: TokenStream = path!("std", "path", "PathBuf", "from")
+        .chain(tokens!(Group::new(Delimiter::Parenthesis, out_dir),))
+        .chain(method("join", Literal::string(file)))
+        .collect();
+    let mut args = path;
+    args.extend(tokens!(
+        Punct::new(',', Spacing::Alone),
+        Literal::string(contents),
+    ));
+
+    let mut result = build_script_output(aliases, iter::empty())?;
+    result.extend(
+        path!("std", "fs", "write")
+            .chain(tokens!(Group::new(Delimiter::Parenthesis, args),))
+            .chain(method("expect", Literal::string("error writing aliases")))
+            .chain(tokens!(Punct::new(';', Spacing::Alone),)),
+    );
+    Ok(TokenTree::Group(Group::new(Delimiter::Brace, result)).into())
+}
+
+fn cfg_macro(operator: &str, args: TokenStream) -> TokenStream {
+    Aliases::get()
+        .and_then(|aliases| {
+            let predicate = aliases.resolve_combinator(
+                Ident::new(operator, Span::call_site()),
+                args,
+            )?;
+            Ok(TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                aliases
+                    .create_trigger()?
+                    .chain(path!("core", "cfg"))
+                    .chain(tokens!(
+                        Punct::new('!', Spacing::Alone),
+                        Group::new(Delimiter::Parenthesis, predicate),
+                    ))
+                    .collect(),
+            ))
+            .into())
+        })
+        .unwrap_or_else(|error| {
+            let mut result = error.into_compile_error();
+            // The invocation must still expand to an expression.
+            if result.is_empty() {
+                result
+                    .extend(tokens!(Ident::new("false", Span::call_site()),));
+            }
+            result
+        })
+}
+
+// Items are split without being parsed. Each ends with a semicolon or a
+// braced group, unless its keyword requires a semicolon, since its value can
+// then contain braces.
+#[derive(Default)]
+struct ItemEnd {
+    semicolon: Option<bool>,
+    qualifier: Option<&'static str>,
+}
+
+impl ItemEnd {
+    // Returns whether the token ends the current item.
+    fn push(&mut self, token: &TokenTree) -> bool {
+        if let Some(qualifier) = self.qualifier.take() {
+            self.semicolon = Some(match (qualifier, token) {
+                ("const", TokenTree::Ident(x)) => {
+                    !["async", "extern", "fn", "unsafe"]
+                        .contains(&&*x.to_string())
+                }
+                ("extern", _) => is_ident(token, "crate"),
+                _ => true,
+            });
+        } else if let (None, TokenTree::Ident(x)) = (self.semicolon, token) {
+            match &*x.to_string() {
+                "pub" => {}
+                "const" => self.qualifier = Some("const"),
+                "extern" => self.qualifier = Some("extern"),
+                "static" | "type" | "use" => self.semicolon = Some(true),
+                _ => self.semicolon = Some(false),
+            }
+        }
+
+        let end = match token {
+            TokenTree::Group(x) => {
+                x.delimiter() == Delimiter::Brace
+                    && self.semicolon == Some(false)
+            }
+            TokenTree::Punct(x) => x.as_char() == ';',
+            _ => false,
+        };
+        if end {
+            self.semicolon = None;
+        }
+        end
+    }
+}
+
+fn split_items(stream: TokenStream) -> Vec<TokenStream> {
+    let mut items = Vec::new();
+    let mut item = Vec::new();
+    let mut item_end = ItemEnd::default();
+    for token in stream {
+        let end = item_end.push(&token);
+        item.push(token);
+        if end {
+            items.push(item.drain(..).collect());
+        }
+    }
+    if !item.is_empty() {
+        items.push(item.into_iter().collect());
+    }
+    items
+}
+
+// Macros cannot observe where they are invoked, so tokens are expanded as an
+// expression when they cannot be items or statements. Those begin with a
+// keyword after their attributes, contain a semicolon, or are invocations of
+// macros using braces.
+fn is_expression(item: &TokenStream) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "enum",
+        "extern",
+        "fn",
+        "impl",
+        "let",
+        "macro_rules",
+        "mod",
+        "pub",
+        "static",
+        "struct",
+        "trait",
+        "type",
+        "use",
+    ];
+
+    let tokens: Vec<_> = item.clone().into_iter().collect();
+    let mut tokens = &tokens[..];
+    while let [TokenTree::Punct(x), TokenTree::Group(_), rest @ ..] = tokens {
+        if x.as_char() != '#' {
+            break;
+        }
+        tokens = rest;
+    }
+    let is_block = |token: Option<&TokenTree>| {
+        matches!(
+            token,
+            Some(TokenTree::Group(x)) if x.delimiter() == Delimiter::Brace,
+        )
+    };
+    match tokens {
+        [] => return false,
+        // Inner attributes can only be written before items.
+        [TokenTree::Punct(x), ..] if x.as_char() == '#' => return false,
+        [TokenTree::Ident(x), rest @ ..] => {
+            let next = rest.first();
+            let item = match &*x.to_string() {
+                // Blocks can be qualified by these keywords.
+                "async" | "const" | "unsafe" => {
+                    !(is_block(next)
+                        || next.is_some_and(|x| is_ident(x, "move")))
+                }
+                "union" => matches!(next, Some(TokenTree::Ident(_))),
+                x => KEYWORDS.contains(&x),
+            };
+            if item {
+                return false;
+            }
+        }
+        _ => {}
+    }
+    if tokens
+        .iter()
+        .any(|x| matches!(x, TokenTree::Punct(x) if x.as_char() == ';'))
+    {
+        return false;
+    }
+    !(tokens.len() >= 2
+        && is_block(tokens.last())
+        && matches!(
+            &tokens[tokens.len() - 2],
+            TokenTree::Punct(x) if x.as_char() == '!',
+        ))
+}
+
+fn cfg_if_condition<I>(
+    aliases: &Aliases,
+    input: &mut I,
+    resolved: &mut bool,
+) -> Result<TokenStream>
+where
+    I: Iterator<Item = TokenTree>,
+{
+    let _ = next!(input, Punct, as_char => '#')?;
+    let attr = next!(input, Group, delimiter => Delimiter::Bracket)?;
+    let mut attr = attr.stream().into_iter();
+    let name = next!(attr, Ident)?;
+    let args = next!(attr, Group, delimiter => Delimiter::Parenthesis)?;
+    parse_empty(attr)?;
+    match &*name.to_string() {
+        "alias" => {
+            *resolved = true;
+            aliases.resolve_combinator(
+                Ident::new("all", name.span()),
+                args.stream(),
+            )
+        }
+        "cfg" => Ok(args.stream()),
+        _ => Err(Error::token(&name.into())),
+    }
+}
+
+fn expand_cfg_if(input: TokenStream) -> Result<TokenStream> {
+    let aliases = Aliases::get()?;
+    let mut input = input.into_iter().peekable();
+    let mut resolved = false;
+    let mut previous = Vec::new();
+    let mut result = TokenStream::new();
+    let _ = next!(input, Ident, to_string => "if")?;
+    let mut condition =
+        Some(cfg_if_condition(&aliases, &mut input, &mut resolved)?);
+    loop {
+        let block = next!(input, Group, delimiter => Delimiter::Brace)?;
+        let span = block.span();
+
+        let predicate =
+            cfg::branch_predicate(condition.clone(), &previous, span);
+        for item in split_items(block.stream()) {
+            result.extend(tokens!(
+                Punct::new('#', Spacing::Joint),
+                Group::new(
+                    Delimiter::Bracket,
+                    tokens!(
+                        Ident::new("cfg", span),
+                        Group::new(Delimiter::Parenthesis, predicate.clone()),
+                    )
+                    .collect(),
+                ),
+            ));
+            result.extend(item);
+        }
+
+        if let Some(condition) = condition.take() {
+            previous.push(condition);
+        } else {
+            parse_empty(input)?;
+            break;
+        }
+        match input.next() {
+            Some(token) if is_ident(&token, "else") => {}
+            Some(token) => return Err(Error::token(&token)),
+            None => break,
+        }
+        if input.next_if(|x| is_ident(x, "if")).is_some() {
+            condition =
+                Some(cfg_if_condition(&aliases, &mut input, &mut resolved)?);
+        }
+    }
+
+    // Markers within the items are resolved as by "eval_block!".
+    let mut tokens = Vec::new();
+    if eval_item(
+        &mut Some(Arc::clone(&aliases)),
+        result.clone(),
+        &mut tokens,
+        &mut resolved,
+        &mut Vec::new(),
+        &Options::default(),
+        0,
+        false,
+    )? {
+        result = tokens.into_iter().collect();
+    }
+    snapshot::write(Span::call_site(), &result);
+    if resolved {
+        result.extend(aliases.create_trigger()?);
+    } else {
+        result.extend(warning::take());
     }
+    Ok(result)
 }
 
 fn parse_empty<I>(tokens: I) -> Result<()>
@@ -215,88 +924,1479 @@ where
 
 type Result<T> = result::Result<T, Error>;
 
-fn eval_item(item: TokenStream, resolved: &mut bool) -> Result<TokenStream> {
+fn is_colon(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(x) if x.as_char() == ':')
+}
+
+fn is_comma(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(x) if x.as_char() == ',')
+}
+
+fn is_ident(token: &TokenTree, name: &str) -> bool {
+    matches!(token, TokenTree::Ident(x) if x.to_string() == name)
+}
+
+// Names from the alias file can be raw identifiers.
+fn ident(name: &str, span: Span) -> Ident {
+    match name.strip_prefix("r#") {
+        Some(name) => Ident::new_raw(name, span),
+        None => Ident::new(name, span),
+    }
+}
+
+// Returns the length of the path to a macro of this crate that ends the
+// tokens. Only full paths are recognized, since other macros could have the
+// same name.
+fn crate_path_len(tokens: &[TokenTree], name: &str) -> Option<usize> {
+    let path = tokens.len().checked_sub(4).map(|x| &tokens[x..])?;
+    if !(is_ident(&path[0], "attr_alias")
+        && is_colon(&path[1])
+        && is_colon(&path[2])
+        && is_ident(&path[3], name))
+    {
+        return None;
+    }
+    Some(
+        if tokens.len() >= 6
+            && is_colon(&tokens[tokens.len() - 5])
+            && is_colon(&tokens[tokens.len() - 6])
+        {
+            6
+        } else {
+            4
+        },
+    )
+}
+
+fn is_nested_eval(attr: TokenStream) -> Result<bool> {
+    let mut attr: Vec<_> = attr.into_iter().collect();
+    let args = match attr.last() {
+        Some(TokenTree::Group(x))
+            if x.delimiter() == Delimiter::Parenthesis =>
+        {
+            Some(x.stream())
+        }
+        _ => None,
+    };
+    if args.is_some() {
+        let _ = attr.pop();
+    }
+    if crate_path_len(&attr, "eval") != Some(attr.len()) {
+        return Ok(false);
+    }
+    // Options are validated, even though they have no effect.
+    args.map(Options::parse).transpose().map(|_| true)
+}
+
+// An item with both a condition and its negation will never be compiled,
+// which is easy to miss when they are expanded from aliases.
+fn warn_conflict(
+    attr: &TokenStream,
+    inner: bool,
+    span: Span,
+    item_attrs: &[((bool, String), Span)],
+) {
+    let Some(negation) = cfg::negate_attr(attr) else {
+        return;
+    };
+    let negation = (inner, negation.to_string());
+    if let Some((_, other_span)) =
+        item_attrs.iter().find(|(x, _)| *x == negation)
+    {
+        Error::spanned(
+            Code::Conflict,
+            span,
+            format!(
+                "`#[{}]` conflicts with `#[{}]`, so this item will never be \
+                 compiled",
+                attr, negation.1,
+            ),
+        )
+        .note(
+            Some(*other_span),
+            "the conflicting attribute is expanded from this marker"
+                .to_owned(),
+        )
+        .warn();
+    }
+}
+
+fn attr_error(error: Error, tokens: &[TokenTree], group: &Group) -> Error {
+    let start = tokens.iter().rev().take(2).find_map(|x| match x {
+        TokenTree::Punct(x) if x.as_char() == '#' => Some(x.span()),
+        _ => None,
+    });
+    error.spanning(start.unwrap_or_else(|| group.span()), group.span())
+}
+
+// Attributes begin with "#", and nested invocations of [eval_block!] include
+// "!", so groups without either can be skipped without collecting their
+// tokens. Comparing identifiers would require allocating strings.
+fn may_contain_markers(stream: TokenStream) -> bool {
+    stream.into_iter().any(|token| match token {
+        TokenTree::Group(x) => may_contain_markers(x.stream()),
+        TokenTree::Punct(x) => matches!(x.as_char(), '#' | '!'),
+        _ => false,
+    })
+}
+
+// A local name bound to an alias by a marker.
+struct Binding {
+    name: String,
+    alias: Ident,
+}
+
+// Tokens are written to a single buffer, into which nested invocations of
+// [eval_block!] are also expanded. Written tokens are sometimes removed, so
+// it cannot be a [TokenStream]. Only those written by this call are
+// inspected. Returns whether they differ from the item.
+//
+// Names bound by inner attributes are available until the end of the group,
+// and those bound by outer attributes until the end of their item. Inner
+// attributes are written first, so the latter are always at the end.
+//
+// Statements and expressions within function bodies are not documented, so
+// attributes affecting only documentation are omitted from their expansions.
+// They would otherwise be reported as unused. Inner attributes at the start
+// of a body still apply to the function and are kept.
+//
+// The alias file is only needed when an attribute might be an alias, so it is
+// loaded by the first one and shared with the rest of the invocation.
+#[allow(clippy::too_many_arguments)]
+fn eval_item(
+    aliases: &mut Option<Arc<Aliases>>,
+    item: TokenStream,
+    tokens: &mut Vec<TokenTree>,
+    resolved: &mut bool,
+    bindings: &mut Vec<Binding>,
+    options: &Options,
+    depth: usize,
+    body: bool,
+) -> Result<bool> {
+    let start = tokens.len();
+    let bindings_start = bindings.len();
+    let mut item_bindings = bindings_start;
+    let mut item_end = ItemEnd::default();
+    let mut function = false;
+    let mut changed = false;
     let mut attr = false;
-    item.into_iter()
-        .map(|mut token| {
-            if let TokenTree::Group(group) = &mut token {
-                let delimiter = group.delimiter();
-                let mut stream = group.stream();
-                if attr && delimiter == Delimiter::Bracket {
-                    *resolved |= Aliases::get()?.resolve(&mut stream)?;
+    let mut macro_call = false;
+    let mut ident = false;
+    let mut nested_block = false;
+    // Attributes resolved for the current item, which are only emitted once.
+    let mut item_attrs = Vec::new();
+    for mut token in item {
+        if mem::take(&mut nested_block)
+            && matches!(&token, TokenTree::Punct(x) if x.as_char() == ';')
+        {
+            continue;
+        }
+
+        // Additional attributes from a bundle must use the same prefix as the
+        // attribute that was resolved.
+        let inner = matches!(
+            tokens[start..].last(),
+            Some(TokenTree::Punct(x)) if x.as_char() == '!',
+        );
+        let attr_group = attr
+            && matches!(
+                &token,
+                TokenTree::Group(x) if x.delimiter() == Delimiter::Bracket,
+            );
+        let mut extra_attrs = Vec::new();
+        if let TokenTree::Group(group) = &mut token {
+            let delimiter = group.delimiter();
+            if attr
+                && delimiter == Delimiter::Bracket
+                && is_nested_eval(group.stream())?
+            {
+                // Remove the attribute, including its "#" and optional "!".
+                while let Some(TokenTree::Punct(x)) = tokens.pop() {
+                    if x.as_char() == '#' {
+                        break;
+                    }
+                }
+                changed = true;
+                attr = false;
+                macro_call = false;
+                ident = false;
+                continue;
+            }
+
+            let nested_len = macro_call
+                .then(|| {
+                    crate_path_len(
+                        &tokens[start..tokens.len() - 1],
+                        "eval_block",
+                    )
+                })
+                .flatten();
+            if let Some(nested_len) = nested_len {
+                // Replace the invocation with its contents, which are
+                // wrapped in a block when they are an expression.
+                tokens.truncate(tokens.len() - nested_len - 1);
+                let item = group.stream();
+                if is_expression(&item) {
+                    let mut block = Vec::new();
+                    let _ = eval_item(
+                        aliases,
+                        item,
+                        &mut block,
+                        resolved,
+                        bindings,
+                        options,
+                        depth + 1,
+                        body,
+                    )?;
+                    tokens.push(
+                        Group::new(
+                            Delimiter::Brace,
+                            block.into_iter().collect(),
+                        )
+                        .into(),
+                    );
+                } else {
+                    let _ = eval_item(
+                        aliases,
+                        item,
+                        tokens,
+                        resolved,
+                        bindings,
+                        options,
+                        depth + 1,
+                        body,
+                    )?;
+                    nested_block = delimiter != Delimiter::Brace;
+                }
+                changed = true;
+                attr = false;
+                macro_call = false;
+                ident = false;
+                continue;
+            }
+
+            // Groups are only rebuilt when they changed, which preserves the
+            // spans of all other tokens.
+            let mut stream = None;
+            if attr_group {
+                let aliases = match aliases {
+                    Some(aliases) => aliases,
+                    None => aliases.insert(Aliases::get()?),
+                };
+                let bound = |name: &Ident| {
+                    let name = name.to_string();
+                    bindings
+                        .iter()
+                        .rev()
+                        .find(|x| x.name == name)
+                        .map(|x| x.alias.clone())
+                };
+                if let Some((name, alias)) =
+                    aliases.parse_binding(&group.stream(), &bound).map_err(
+                        |error| attr_error(error, &tokens[start..], group),
+                    )?
+                {
+                    *resolved = true;
+                    bindings.push(Binding {
+                        name: name.to_string(),
+                        alias,
+                    });
+                    if inner {
+                        item_bindings = bindings.len();
+                    }
+                    // The marker is removed with its "#" and optional "!".
+                    while let Some(TokenTree::Punct(x)) = tokens.pop() {
+                        if x.as_char() == '#' {
+                            break;
+                        }
+                    }
+                    changed = true;
+                    attr = false;
+                    macro_call = false;
+                    ident = false;
+                    continue;
+                }
+                let attr_stream = if bindings.is_empty() {
+                    group.stream()
                 } else {
-                    stream = eval_item(stream, resolved)?;
+                    aliases::bind_names(group.stream(), &bound)
                 };
+                if let Some(attrs) =
+                    aliases.resolve(attr_stream, group.span()).map_err(
+                        |error| attr_error(error, &tokens[start..], group),
+                    )?
+                {
+                    *resolved = true;
+                    let span = group.span();
+                    let mut attrs = attrs.into_iter().filter(|x| {
+                        if body && !inner && cfg::is_doc_attr(x) {
+                            return false;
+                        }
+                        let key = (inner, x.to_string());
+                        let unique =
+                            !item_attrs.iter().any(|(x, _)| *x == key);
+                        if unique {
+                            warn_conflict(x, inner, span, &item_attrs);
+                            item_attrs.push((key, span));
+                        }
+                        unique
+                    });
+                    let Some(attr) = attrs.next() else {
+                        // Every attribute was a duplicate, so the marker is
+                        // removed with its "#" and optional "!".
+                        while let Some(TokenTree::Punct(x)) = tokens.pop() {
+                            if x.as_char() == '#' {
+                                break;
+                            }
+                        }
+                        changed = true;
+                        attr = false;
+                        macro_call = false;
+                        ident = false;
+                        continue;
+                    };
+                    stream = Some(attr);
+                    extra_attrs.extend(attrs);
+                }
+            } else if !options.skips(depth, macro_call)
+                && may_contain_markers(group.stream())
+            {
+                let mut group_tokens = Vec::new();
+                if eval_item(
+                    aliases,
+                    group.stream(),
+                    &mut group_tokens,
+                    resolved,
+                    bindings,
+                    options,
+                    depth + 1,
+                    body || (function && delimiter == Delimiter::Brace),
+                )? {
+                    stream = Some(group_tokens.into_iter().collect());
+                }
+            };
+            if let Some(stream) = stream {
+                changed = true;
+                let span = group.span();
                 *group = Group::new(delimiter, stream);
+                group.set_span(span);
             }
-            attr = matches!(
-                &token,
-                TokenTree::Punct(x)
-                    if x.as_char() == '#' || (attr && x.as_char() == '!'),
-            );
-            Ok(token)
-        })
-        .collect()
+        }
+        attr = matches!(
+            &token,
+            TokenTree::Punct(x)
+                if x.as_char() == '#' || (attr && x.as_char() == '!'),
+        );
+        // Macro invocations are identified by a path followed by "!".
+        // Definitions using "macro_rules!" also have a name before the body.
+        macro_call = match &token {
+            TokenTree::Ident(_) => macro_call,
+            TokenTree::Punct(x) => ident && x.as_char() == '!',
+            _ => false,
+        };
+        ident = matches!(token, TokenTree::Ident(_));
+        if !attr && !attr_group {
+            item_attrs.clear();
+        }
+        if is_ident(&token, "fn") {
+            function = true;
+        }
+        if item_end.push(&token) {
+            bindings.truncate(item_bindings);
+            function = false;
+        }
+
+        tokens.push(token);
+        for attr in extra_attrs {
+            tokens.push(Punct::new('#', Spacing::Joint).into());
+            if inner {
+                tokens.push(Punct::new('!', Spacing::Alone).into());
+            }
+            tokens.push(Group::new(Delimiter::Bracket, attr).into());
+        }
+    }
+    bindings.truncate(bindings_start);
+    Ok(changed)
 }
 
-/// Resolves an alias using a pattern.
-///
-/// # Arguments
-///
-/// The following positional arguments are expected:
-/// 1. *alias name* - required and must be a valid [Rust identifier]
-/// 2. *expansion pattern* - optional and may include `*` wildcards
-///     - The first wildcard in this pattern will be replaced with the expanded
-///       alias.
-///     - If not specified, this argument defaults to the value of the
-///       "default" alias, or `*` if that alias is not defined.
+// The trigger and warnings are items, so expressions are expanded as a block
+// beginning with them.
+fn eval_with(
+    item: TokenStream,
+    options: Options,
+    expression: bool,
+) -> TokenStream {
+    let finish = |result: TokenStream, items: TokenStream| {
+        if expression {
+            let mut block = items;
+            block.extend(result);
+            TokenTree::Group(Group::new(Delimiter::Brace, block)).into()
+        } else {
+            let mut result = result;
+            result.extend(items);
+            result
+        }
+    };
+
+    let mut aliases = None;
+    let mut resolved = false;
+    let mut tokens = Vec::new();
+    let result = match eval_item(
+        &mut aliases,
+        item.clone(),
+        &mut tokens,
+        &mut resolved,
+        &mut Vec::new(),
+        &options,
+        0,
+        false,
+    ) {
+        Ok(true) => tokens.into_iter().collect(),
+        Ok(false) => item,
+        // Tools such as rust-analyzer may expand items before the alias
+        // file exists or from another directory, so the item is kept for
+        // them. Markers within it will still cause errors when compiling.
+        Err(error) if matches!(error.code, Code::Reading) => {
+            error.warn();
+            return finish(item, warning::take().collect());
+        }
+        Err(error) => {
+            return finish(TokenStream::new(), error.into_compile_error());
+        }
+    };
+    snapshot::write(Span::call_site(), &result);
+
+    if !resolved && options.allow_unused {
+        return finish(result, warning::take().collect());
+    }
+    let trigger = if resolved {
+        aliases
+            .map_or_else(Aliases::get, Ok)
+            .and_then(|x| x.create_trigger())
+    } else {
+        Err(Error::new(Code::Unnecessary, "unnecessary attribute"))
+    };
+    let items = match trigger {
+        Ok(trigger) => trigger.collect(),
+        Err(error) => error.into_compile_error(),
+    };
+    finish(result, items)
+}
+
+// Resolution is deferred by moving this attribute directly before the first
+// attribute that must be resolved, without the "after" option. Attributes
+// are expanded in order, so those before it will be expanded first.
+fn defer(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let mut args = args.into_iter().peekable();
+    let mut new_args = TokenStream::new();
+    while args.peek().is_some() {
+        let arg: Vec<_> = args.by_ref().take_while(|x| !is_comma(x)).collect();
+        if !matches!(&arg[..], [x] if is_ident(x, "after")) {
+            new_args.extend(arg);
+            new_args.extend(tokens!(Punct::new(',', Spacing::Alone),));
+        }
+    }
+
+    let mut item = item.into_iter().peekable();
+    let mut result = TokenStream::new();
+    let mut aliases = None;
+    while let Some(TokenTree::Punct(x)) = item.peek() {
+        if x.as_char() != '#' {
+            break;
+        }
+        let span = x.span();
+        let mut attr = item.clone().skip(1);
+        if let Some(TokenTree::Group(group)) = attr.next() {
+            let aliases = match &mut aliases {
+                Some(aliases) => aliases,
+                None => aliases.insert(Aliases::get()?),
+            };
+            if aliases
+                .parse_binding(&group.stream(), &|_| None)
+                .and_then(|binding| {
+                    if binding.is_some() {
+                        return Ok(true);
+                    }
+                    aliases
+                        .resolve(group.stream(), group.span())
+                        .map(|x| x.is_some())
+                })
+                .map_err(|error| error.spanning(span, group.span()))?
+            {
+                break;
+            }
+        }
+        result.extend(item.by_ref().take(2));
+    }
+    result.extend(tokens!(
+        Punct::new('#', Spacing::Alone),
+        Group::new(
+            Delimiter::Bracket,
+            path!("attr_alias", "eval")
+                .chain(tokens!(Group::new(Delimiter::Parenthesis, new_args),))
+                .collect(),
+        ),
+    ));
+    result.extend(item);
+    result.extend(warning::take());
+    Ok(result)
+}
+
+// Non-inline modules can only be passed to attribute macros using the
+// unstable "proc_macro_hygiene" feature. The compiler's error for them does
+// not mention the alternative, but the nightly feature of this crate
+// indicates that the unstable feature may be enabled.
+#[cfg(not(feature = "nightly"))]
+fn check_inline_module(item: TokenStream) -> Result<()> {
+    let mut item = item.into_iter().peekable();
+    while item
+        .next_if(|x| matches!(x, TokenTree::Punct(x) if x.as_char() == '#'))
+        .is_some()
+    {
+        let _ = item.next();
+    }
+    if item.next_if(|x| is_ident(x, "pub")).is_some() {
+        let _ = item.next_if(|x| {
+            matches!(
+                x,
+                TokenTree::Group(x) if x.delimiter() == Delimiter::Parenthesis,
+            )
+        });
+    }
+    let _ = item.next_if(|x| is_ident(x, "unsafe"));
+    let Some(module) = item.next_if(|x| is_ident(x, "mod")) else {
+        return Ok(());
+    };
+    if !matches!(
+        (item.next(), item.next(), item.next()),
+        (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(x)), None)
+            if x.as_char() == ';'
+    ) {
+        return Ok(());
+    }
+    Err(Error::spanned(
+        Code::Module,
+        module.span(),
+        "non-inline modules cannot be annotated by this attribute \
+                  without an unstable feature"
+            .to_owned(),
+    )
+    .note(
+        None,
+        "use `attr_alias::eval_block! { ... }` around the module instead"
+            .to_owned(),
+    ))
+}
+
+/// Expands to a module containing the value of each alias as a constant.
 ///
-/// For example, using the [example alias file], the annotations
-/// `#[attr_alias(macos, cfg(*))]` and `#[attr_alias(macos)]` would both expand
-/// to `#[cfg(target_os = "macos")]`.
+/// The module is named `aliases`, and each constant is named by its alias in
+/// uppercase. Values are defined in the same order as the [alias file], with
+/// references to other aliases resolved. The default alias is omitted, since
+/// it is a pattern. Logging, error messages, and tests can then refer to the
+/// exact predicates used by the code.
 ///
 /// # Examples
 ///
 /// *Compiled using the [example alias file].*
 ///
 /// ```
-/// # #![cfg_attr(feature = "nightly", feature(doc_cfg))]
-/// #
-/// use std::process::Command;
-///
-/// use attr_alias::attr_alias;
-///
-/// struct ProcessBuilder(Command);
+/// attr_alias::alias_consts!();
 ///
-/// impl ProcessBuilder {
-///     #[attr_alias(macos_or_windows)]
-#[cfg_attr(
-    feature = "nightly",
-    doc = "    #[attr_alias(macos_or_windows, doc(cfg(*)))]"
-)]
-///     fn name(&mut self, name: &str) -> &mut Self {
-///         unimplemented!();
-///     }
-/// }
+/// assert_eq!(r#"target_os = "macos""#, aliases::MACOS);
+/// assert_eq!(
+///     r#"any(target_os = "macos", windows)"#,
+///     aliases::MACOS_OR_WINDOWS,
+/// );
 /// ```
 ///
+/// [alias file]: self#alias-file
 /// [example alias file]: self#example
-/// [Rust identifier]: https://doc.rust-lang.org/reference/identifiers.html
+#[proc_macro]
+pub fn alias_consts(args: TokenStream) -> TokenStream {
+    fn doc(text: &str) -> impl Iterator<Item = TokenTree> {
+        tokens!(
+            Punct::new('#', Spacing::Joint),
+            Group::new(
+                Delimiter::Bracket,
+                tokens!(
+                    Ident::new("doc", Span::call_site()),
+                    Punct::new('=', Spacing::Alone),
+                    Literal::string(text),
+                )
+                .collect(),
+            ),
+        )
+    }
+
+    parse_empty(args)
+        .and_then(|()| {
+            let aliases = Aliases::get()?;
+            let mut consts: TokenStream = aliases.create_trigger()?.collect();
+            for (name, value, _) in aliases.expansions()? {
+                let name = name.strip_prefix("r#").unwrap_or(name);
+                consts.extend(doc(&format!(
+                    " The value of the \"{}\" alias.",
+                    name,
+                )));
+                consts.extend(tokens!(
+                    Ident::new("pub", Span::call_site()),
+                    Ident::new("const", Span::call_site()),
+                    Ident::new(&name.to_uppercase(), Span::call_site()),
+                    Punct::new(':', Spacing::Alone),
+                    Punct::new('&', Spacing::Alone),
+                    Ident::new("str", Span::call_site()),
+                    Punct::new('=', Spacing::Alone),
+                    Literal::string(value),
+                    Punct::new(';', Spacing::Alone),
+                ));
+            }
+
+            Ok(doc(" The values of aliases in the alias file.")
+                .chain(aliases.lint_attrs())
+                .chain(tokens!(
+                    Ident::new("pub", Span::call_site()),
+                    Ident::new("mod", Span::call_site()),
+                    Ident::new("aliases", Span::call_site()),
+                    Group::new(Delimiter::Brace, consts),
+                ))
+                .collect())
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Expands to a declarative macro for each alias, which applies its
+/// attributes to the item it is given.
+///
+/// Each macro is named by its alias and expands to the attributes that the
+/// alias expands to without a pattern, followed by its input. The default
+/// alias is omitted, since it is a pattern. The macros can be applied as
+/// attributes using `#[apply]` from [macro\_rules\_attribute], so crates
+/// migrating from that crate can use the [alias file] for both. They are
+/// defined where this macro is called, so it should be called before the
+/// modules using them. Macros named by keywords, such as `gen` or `async`,
+/// are defined as raw identifiers, since only the invoking crate knows which
+/// keywords its edition reserves.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// ```
+/// attr_alias::alias_macros!();
+///
+/// value_type! {
+///     struct Point(u32, u32);
+/// }
+///
+/// let point = Point(1, 2);
+/// assert_eq!(point, point.clone());
+/// ```
+///
+/// With macro\_rules\_attribute:
+///
+/// ```ignore
+/// use macro_rules_attribute::apply;
+///
+/// attr_alias::alias_macros!();
+///
+/// #[apply(value_type)]
+/// struct Point(u32, u32);
+/// ```
+///
+/// [alias file]: self#alias-file
+/// [example alias file]: self#example
+/// [macro\_rules\_attribute]: https://crates.io/crates/macro_rules_attribute
+#[proc_macro]
+pub fn alias_macros(args: TokenStream) -> TokenStream {
+    parse_empty(args)
+        .and_then(|()| {
+            let aliases = Aliases::get()?;
+            let mut result: TokenStream = aliases.create_trigger()?.collect();
+            for (name, _, attrs) in aliases.expansions()? {
+                // The edition of the invoking crate is not known, and some
+                // keywords are only reserved by later editions, so all
+                // keywords that can be raw are.
+                let name = name.strip_prefix("r#").unwrap_or(name);
+                let name = if names::KEYWORDS.contains(&name)
+                    && !["crate", "self", "Self", "super"].contains(&name)
+                {
+                    Ident::new_raw(name, Span::call_site())
+                } else {
+                    Ident::new(name, Span::call_site())
+                };
+                let mut body: TokenStream = attrs
+                    .into_iter()
+                    .flat_map(|attr| {
+                        tokens!(
+                            Punct::new('#', Spacing::Joint),
+                            Group::new(Delimiter::Bracket, attr),
+                        )
+                    })
+                    .collect();
+                body.extend(tokens!(
+                    Punct::new('$', Spacing::Joint),
+                    Group::new(
+                        Delimiter::Parenthesis,
+                        tokens!(
+                            Punct::new('$', Spacing::Joint),
+                            Ident::new("item", Span::call_site()),
+                        )
+                        .collect(),
+                    ),
+                    Punct::new('*', Spacing::Alone),
+                ));
+                let pattern = tokens!(
+                    Punct::new('$', Spacing::Joint),
+                    Group::new(
+                        Delimiter::Parenthesis,
+                        tokens!(
+                            Punct::new('$', Spacing::Joint),
+                            Ident::new("item", Span::call_site()),
+                            Punct::new(':', Spacing::Alone),
+                            Ident::new("tt", Span::call_site()),
+                        )
+                        .collect(),
+                    ),
+                    Punct::new('*', Spacing::Alone),
+                );
+
+                // Many of the macros will not be used by each crate.
+                result.extend(aliases.lint_attrs());
+                result.extend(tokens!(
+                    Punct::new('#', Spacing::Joint),
+                    Group::new(
+                        Delimiter::Bracket,
+                        tokens!(
+                            Ident::new("allow", Span::call_site()),
+                            Group::new(
+                                Delimiter::Parenthesis,
+                                tokens!(Ident::new(
+                                    "unused_macros",
+                                    Span::call_site(),
+                                ),)
+                                .collect(),
+                            ),
+                        )
+                        .collect(),
+                    ),
+                    Ident::new("macro_rules", Span::call_site()),
+                    Punct::new('!', Spacing::Alone),
+                    name,
+                    Group::new(
+                        Delimiter::Brace,
+                        tokens!(
+                            Group::new(
+                                Delimiter::Parenthesis,
+                                pattern.collect(),
+                            ),
+                            Punct::new('=', Spacing::Joint),
+                            Punct::new('>', Spacing::Alone),
+                            Group::new(Delimiter::Brace, body),
+                            Punct::new(';', Spacing::Alone),
+                        )
+                        .collect(),
+                    ),
+                ));
+            }
+            Ok(result)
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Combines aliases using `all()`.
+///
+/// This macro is equivalent to [`any!`], except that all aliases must be
+/// enabled for the predicate to be true.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// ```
+/// assert_eq!(
+///     cfg!(target_os = "macos"),
+///     attr_alias::all!(macos, supported_platform),
+/// );
+/// ```
+///
+/// [example alias file]: self#example
+#[proc_macro]
+pub fn all(args: TokenStream) -> TokenStream {
+    cfg_macro("all", args)
+}
+
+/// Combines aliases using `any()`.
+///
+/// Each argument must be the name of an alias whose value is a configuration
+/// predicate. This macro expands to a [`cfg!`] expression for the combined
+/// predicate.
+///
+/// Within [`#[eval]`][macro@eval] and [`eval_block!`], `any!(...)` and
+/// `all!(...)` can also be written inside of attributes, where they will
+/// expand to the combined predicate itself.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// ```
+/// if attr_alias::any!(macos, supported_platform) {
+///     println!("this platform is supported");
+/// }
+/// ```
+///
+/// Within an attribute:
+///
+/// ```
+/// #[attr_alias::eval]
+/// mod platform {
+///     #[cfg(any!(macos, supported_platform))]
+///     pub(super) fn run() {}
+/// }
+/// #
+/// # platform::run();
+/// ```
+///
+/// [example alias file]: self#example
+#[proc_macro]
+pub fn any(args: TokenStream) -> TokenStream {
+    cfg_macro("any", args)
+}
+
+// Escapes are decoded as by the compiler, including line continuations.
+// Suffixes are not allowed.
+#[cfg(feature = "testing")]
+fn string_value(literal: &Literal) -> Option<String> {
+    let text = literal.to_string();
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = &raw[..raw.len() - raw.trim_start_matches('#').len()];
+        return raw
+            .strip_prefix(hashes)?
+            .strip_prefix('"')?
+            .strip_suffix(hashes)?
+            .strip_suffix('"')
+            .map(ToOwned::to_owned);
+    }
+
+    let mut chars = text.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut value = String::new();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            value.push(ch);
+            continue;
+        }
+        let ch = match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            'x' => {
+                let code: String = chars.by_ref().take(2).collect();
+                u8::from_str_radix(&code, 16).ok()?.into()
+            }
+            'u' => {
+                let code: String =
+                    chars.by_ref().skip(1).take_while(|&x| x != '}').collect();
+                char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+            }
+            '\n' => {
+                chars = chars.as_str().trim_start().chars();
+                continue;
+            }
+            ch => ch,
+        };
+        value.push(ch);
+    }
+    Some(value)
+}
+
+/// Asserts how an item expands using an inline table of aliases.
+///
+/// This macro is provided by the **testing** feature. It allows unit tests
+/// to check expansions without depending on the [alias file] of the package.
+///
+/// The following positional arguments are expected:
+/// 1. *aliases* - a string literal, which is parsed in the same way as the
+///    [alias file] and replaces it for this invocation
+///     - Leading whitespace is removed from each line, so the table can be
+///       indented.
+/// 2. *options* (optional) - [arguments] accepted by [`eval_block!`]
+/// 3. *input* - tokens wrapped in braces, which are expanded in the same
+///    way as by [`eval_block!`]
+/// 4. *expected tokens* - tokens wrapped in braces
+///
+/// The macro expands to [`assert_eq!`], comparing the expanded tokens to the
+/// expected tokens after both are formatted in the same way. Therefore, it
+/// should be called within a test, where a failure will display both.
+/// Errors are reported when compiling, with locations referring to lines of
+/// the table, and the alias file is not needed.
+///
+/// # Examples
+///
+/// ```
+/// attr_alias::assert_expansion!(
+///     r#"
+///         *default = cfg(*)
+///         *macos = target_os = "macos"
+///         *strict = forbid(unsafe_code); warn(unused_results);
+///     "#,
+///     {
+///         #[attr_alias(macos)]
+///         #[attr_alias(strict)]
+///         fn macos() {}
+///     },
+///     {
+///         #[cfg(target_os = "macos")]
+///         #[forbid(unsafe_code)]
+///         #[warn(unused_results)]
+///         fn macos() {}
+///     },
+/// );
+/// ```
+///
+/// [alias file]: self#alias-file
+/// [arguments]: macro@eval#arguments
+#[cfg(feature = "testing")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "testing")))]
+#[proc_macro]
+pub fn assert_expansion(args: TokenStream) -> TokenStream {
+    fn format(stream: TokenStream) -> Literal {
+        let text = stream.to_string();
+        Literal::string(
+            &TokenStream::from_str(&text).map_or(text, |x| x.to_string()),
+        )
+    }
+
+    let mut args = args.into_iter();
+    let mut parse = || {
+        let literal = next!(args, Literal)?;
+        let table = string_value(&literal).ok_or_else(|| {
+            Error::spanned(
+                Code::Syntax,
+                literal.span(),
+                "expected a string literal".to_owned(),
+            )
+        })?;
+        let table: Vec<_> = table.split('\n').map(str::trim_start).collect();
+        let _ = next!(args, Punct, as_char => ',')?;
+        // Options are separated from the input in the same way as for
+        // eval_block!.
+        let mut options = Vec::new();
+        let input = loop {
+            match args.next() {
+                Some(TokenTree::Group(group))
+                    if group.delimiter() == Delimiter::Brace =>
+                {
+                    break group.stream();
+                }
+                Some(token) => options.push(token),
+                None => {
+                    return Err(Error::new(
+                        Code::Syntax,
+                        "unexpected end of tokens",
+                    ));
+                }
+            }
+        };
+        let options = match options.pop() {
+            Some(comma) if is_comma(&comma) => {
+                Options::parse(options.into_iter().collect())?
+            }
+            Some(token) => return Err(Error::token(&token)),
+            None => Options::default(),
+        };
+        if options.after {
+            return Err(Error::new(
+                Code::Option,
+                "option 'after' is only supported by #[eval]",
+            ));
+        }
+        let mut groups = vec![input];
+        let _ = next!(args, Punct, as_char => ',')?;
+        let group = next!(args, Group)?;
+        if group.delimiter() != Delimiter::Brace {
+            return Err(Error::token(&group.into()));
+        }
+        groups.push(group.stream());
+        if let Some(token) = args.next() {
+            if !is_comma(&token) {
+                return Err(Error::token(&token));
+            }
+            parse_empty(args.by_ref())?;
+        }
+        Ok((table.join("\n"), options, groups))
+    };
+    parse()
+        .and_then(|(table, options, mut groups)| {
+            let expected = groups.pop().expect("missing expected tokens");
+            let input = groups.pop().expect("missing input");
+            let mut tokens = Vec::new();
+            let _ = Aliases::with_table(table, || {
+                eval_item(
+                    &mut None,
+                    input,
+                    &mut tokens,
+                    &mut false,
+                    &mut Vec::new(),
+                    &options,
+                    0,
+                    false,
+                )
+            })??;
+
+            let mut result: TokenStream = warning::take().collect();
+            result.extend(path!("core", "assert_eq"));
+            result.extend(tokens!(
+                Punct::new('!', Spacing::Alone),
+                Group::new(
+                    Delimiter::Parenthesis,
+                    tokens!(
+                        format(tokens.into_iter().collect()),
+                        Punct::new(',', Spacing::Alone),
+                        format(expected),
+                    )
+                    .collect(),
+                ),
+            ));
+            Ok(tokens!(Group::new(Delimiter::Brace, result),).collect())
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Resolves an alias using a pattern.
+///
+/// # Arguments
+///
+/// The following positional arguments are expected:
+/// 1. *alias names* - at least one is required, and each must be a valid
+///    [Rust identifier]
+///     - A name can be negated as `!name` or `not(name)`, which wraps the
+///       value of the alias in `not(...)` before it is substituted. Only
+///       aliases whose values are single configuration predicates can be
+///       negated.
+/// 2. *expansion pattern* - optional and may include `*` wildcards
+///     - The pattern must include one wildcard for each alias name, which
+///       will be replaced with the expanded aliases in order.
+///     - Other aliases can be referenced anywhere within this pattern.
+///     - Multiple attributes can be separated by `;`, and the aliases will be
+///       substituted into each of them. For example,
+///       `#[attr_alias(macos, cfg(*); doc(cfg(*)))]` would expand to both
+///       `#[cfg(target_os = "macos")]` and
+///       `#[doc(cfg(target_os = "macos"))]`.
+///     - If not specified, this argument defaults to the value of the
+///       "default" alias, or `*` if that alias is not defined.
+///
+/// An error will be reported if the number of wildcards differs from the
+/// number of aliases given.
+///
+/// The "default" alias cannot be used as the alias name, since it is applied
+/// implicitly. When it should be expanded explicitly, such as to nest it
+/// within another pattern, the raw identifier `r#default` can be used instead.
+///
+/// For example, using the [example alias file], the following annotations
+/// would expand as shown:
+/// - `#[attr_alias(macos)]` and `#[attr_alias(macos, cfg(*))]` -
+///   `#[cfg(target_os = "macos")]`
+/// - `#[attr_alias(!macos)]` -
+///   `#[cfg(not(target_os = "macos"))]`
+/// - `#[attr_alias(macos, supported_platform, cfg(all(*, *)))]` -
+///   `#[cfg(all(target_os = "macos", any(unix, windows)))]`
+/// - `#[attr_alias(macos, cfg_attr(docsrs, attr_alias(r#default)))]` -
+///   `#[cfg_attr(docsrs, cfg(target_os = "macos"))]`
+///
+/// The following keyword arguments can also be specified:
+/// - `level = <lint level>` -
+///   Replaces the level of each expanded lint attribute. For example,
+///   `#[attr_alias(strict, level = forbid)]` would expand to
+///   `#[forbid(unsafe_code)]` and `#[forbid(unused_results)]`.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(doc_cfg))]
+/// #
+/// use std::process::Command;
+///
+/// use attr_alias::attr_alias;
+///
+/// struct ProcessBuilder(Command);
+///
+/// impl ProcessBuilder {
+///     #[attr_alias(macos_or_windows)]
+#[cfg_attr(
+    feature = "nightly",
+    doc = "    #[attr_alias(macos_or_windows, doc(cfg(*)))]"
+)]
+///     fn name(&mut self, name: &str) -> &mut Self {
+///         unimplemented!();
+///     }
+/// }
+/// ```
+///
+/// [example alias file]: self#example
+/// [Rust identifier]: https://doc.rust-lang.org/reference/identifiers.html
 #[cfg(feature = "nightly")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "nightly")))]
 #[proc_macro_attribute]
 pub fn attr_alias(args: TokenStream, item: TokenStream) -> TokenStream {
-    tracked_path::path(Aliases::FILE);
+    if let Ok(path) = Aliases::path() {
+        tracked::path(path);
+    }
 
     Aliases::get()
-        .and_then(|x| x.resolve_args(args))
-        .map(|alias| {
-            tokens!(
-                Punct::new('#', Spacing::Joint),
-                Group::new(Delimiter::Bracket, alias),
-            )
-            .chain(item)
-            .collect()
+        .and_then(|x| x.resolve_args(args, Span::call_site()))
+        .map(|attrs| {
+            attrs
+                .into_iter()
+                .flat_map(|attr| {
+                    tokens!(
+                        Punct::new('#', Spacing::Joint),
+                        Group::new(Delimiter::Bracket, attr),
+                    )
+                })
+                .chain(item)
+                .collect()
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Expands to an invocation of [`cfg_aliases!`] defining the same aliases.
+///
+/// This macro allows the [alias file] to be used by crates that also use the
+/// [cfg\_aliases] crate, or that are migrating to or from it. It should be
+/// called by a build script with both crates added as build dependencies:
+///
+/// ```toml
+/// [build-dependencies]
+/// attr_alias = "0.1"
+/// cfg_aliases = "0.2"
+/// ```
+///
+/// Only aliases that expand to a `cfg` attribute without a pattern can be
+/// represented, so others are omitted. References to other aliases will have
+/// been resolved. The build script will also run again when the
+/// [alias file] changes.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// In "build.rs":
+///
+/// ```ignore
+/// attr_alias::cfg_aliases!();
+/// ```
+///
+/// It will expand to the following invocation:
+///
+/// ```ignore
+/// cfg_aliases::cfg_aliases! {
+///     macos: { target_os = "macos" },
+///     macos_or_windows: { any(target_os = "macos", windows) },
+///     supported_platform: { any(unix, windows) },
+/// }
+/// ```
+///
+/// [alias file]: self#alias-file
+/// [cfg\_aliases]: https://crates.io/crates/cfg_aliases
+/// [`cfg_aliases!`]: https://docs.rs/cfg_aliases/latest/cfg_aliases/macro.cfg_aliases.html
+/// [example alias file]: self#example
+#[proc_macro]
+pub fn cfg_aliases(args: TokenStream) -> TokenStream {
+    parse_empty(args)
+        .and_then(|()| {
+            let aliases = Aliases::get()?;
+            let mut definitions = TokenStream::new();
+            for (name, predicate) in aliases.predicates()? {
+                definitions.extend(tokens!(
+                    ident(name, Span::call_site()),
+                    Punct::new(':', Spacing::Alone),
+                    Group::new(Delimiter::Brace, predicate),
+                    Punct::new(',', Spacing::Alone),
+                ));
+            }
+
+            let mut result = build_script_output(&aliases, iter::empty())?;
+            result.extend(path!("cfg_aliases", "cfg_aliases").chain(tokens!(
+                Punct::new('!', Spacing::Alone),
+                Group::new(Delimiter::Brace, definitions),
+            )));
+            Ok(TokenTree::Group(Group::new(Delimiter::Brace, result)).into())
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Expands to the `--cfg` flags that enable aliases.
+///
+/// Each argument must be the name of an alias whose value is a configuration
+/// predicate. This macro expands to a string literal containing the flags that
+/// must be passed to the compiler for all of the predicates to be true,
+/// separated by spaces. It allows scripts to configure builds using the same
+/// definitions as the code, such as by setting the `RUSTFLAGS` environment
+/// variable.
+///
+/// The predicates can only combine options using `all()`, and names set by the
+/// compiler cannot be used. Values containing whitespace cannot be passed
+/// using `RUSTFLAGS`, since it is split on whitespace.
+///
+/// # Examples
+///
+/// With the following [alias file]:
+///
+/// ```text
+/// *nightly_docs=all(docsrs, nightly)
+/// ```
+///
+/// In "examples/cfg\_flags.rs":
+///
+/// ```ignore
+/// fn main() {
+///     println!("{}", attr_alias::cfg_flags!(nightly_docs));
+/// }
+/// ```
+///
+/// It will print `--cfg docsrs --cfg nightly`, which can be used by a script:
+///
+/// ```sh
+/// RUSTDOCFLAGS="$(cargo run -q --example cfg_flags)" cargo doc
+/// ```
+///
+/// [alias file]: self#alias-file
+#[proc_macro]
+pub fn cfg_flags(args: TokenStream) -> TokenStream {
+    Aliases::get()
+        .and_then(|aliases| {
+            let predicate = aliases.resolve_combinator(
+                Ident::new("all", Span::call_site()),
+                args,
+            )?;
+            let options =
+                cfg::required_options(predicate).map_err(|predicate| {
+                    Error::spanned(
+                        Code::UnsettableCfg,
+                        Span::call_site(),
+                        format!(
+                            "predicate `{}` cannot be enabled by flags",
+                            predicate,
+                        ),
+                    )
+                })?;
+            let mut flags = Vec::new();
+            for (name, value) in options {
+                if names::WELL_KNOWN_NAMES.contains(&&*name.to_string()) {
+                    return Err(Error::spanned(
+                        Code::UnsettableCfg,
+                        name.span(),
+                        format!(
+                            "configuration name '{}' is set by the compiler",
+                            name,
+                        ),
+                    ));
+                }
+                let flag = value.map_or_else(
+                    || format!("--cfg {}", name),
+                    |value| format!("--cfg {}={}", name, value),
+                );
+                if !flags.contains(&flag) {
+                    flags.push(flag);
+                }
+            }
+            Ok(TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                aliases
+                    .create_trigger()?
+                    .chain(tokens!(Literal::string(&flags.join(" ")),))
+                    .collect(),
+            ))
+            .into())
+        })
+        .unwrap_or_else(|error| {
+            let mut result = error.into_compile_error();
+            // The invocation must still expand to an expression.
+            if result.is_empty() {
+                result.extend(tokens!(Literal::string(""),));
+            }
+            result
+        })
+}
+
+/// Selects items using aliases, in the style of the [cfg-if] crate.
+///
+/// Each condition is written as `#[alias(...)]`, with the names of aliases
+/// whose values are configuration predicates, or as `#[cfg(...)]`, with a
+/// predicate written directly. When multiple names are given, all of them must
+/// be enabled, as for [`all!`]. The items of the first branch whose condition
+/// is true will be compiled. A final `else` branch can be given for when none
+/// of them are true.
+///
+/// Every item within a branch is annotated by a `cfg` attribute that also
+/// excludes the conditions of earlier branches. Markers within the items are
+/// then resolved, as for [`eval_block!`].
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// ```
+/// attr_alias::cfg_if! {
+///     if #[alias(macos)] {
+///         fn platform() -> &'static str {
+///             "macOS"
+///         }
+///     } else if #[cfg(windows)] {
+///         fn platform() -> &'static str {
+///             "Windows"
+///         }
+///     } else {
+///         #[attr_alias(supported_platform)]
+///         fn platform() -> &'static str {
+///             "Unix"
+///         }
+///     }
+/// }
+/// #
+/// # let _ = platform();
+/// ```
+///
+/// [cfg-if]: https://crates.io/crates/cfg-if
+/// [example alias file]: self#example
+#[proc_macro]
+pub fn cfg_if(input: TokenStream) -> TokenStream {
+    expand_cfg_if(input).unwrap_or_else(Error::into_compile_error)
+}
+
+/// Prints the `check-cfg` configuration for custom names used by aliases.
+///
+/// Since Rust 1.80, configuration names that are not declared are reported
+/// by the `unexpected_cfgs` lint. When this macro is called by a build script,
+/// each custom name used by `cfg` and `cfg_attr` attributes in the expansions
+/// of aliases without a pattern will be declared, along with the values it is
+/// compared to. [Well-known names] and `feature` are not declared. The build
+/// script will also run again when the [alias file] changes.
+///
+/// This crate must be added as a build dependency:
+///
+/// ```toml
+/// [build-dependencies]
+/// attr_alias = "0.1"
+/// ```
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// In "build.rs":
+///
+/// ```
+/// attr_alias::check_cfg!();
+/// ```
+///
+/// [alias file]: self#alias-file
+/// [example alias file]: self#example
+/// [Well-known names]: https://doc.rust-lang.org/rustc/check-cfg.html#well-known-names-and-values
+#[proc_macro]
+pub fn check_cfg(args: TokenStream) -> TokenStream {
+    parse_empty(args)
+        .and_then(|()| Aliases::get())
+        .and_then(|aliases| {
+            let cfgs = aliases.custom_cfgs()?;
+            let lines = cfgs.into_iter().map(|(name, values)| {
+                let values: Vec<_> = values
+                    .into_iter()
+                    .map(|x| x.unwrap_or_else(|| "none()".to_owned()))
+                    .collect();
+                if values == ["none()"] {
+                    format!("cargo:rustc-check-cfg=cfg({})", name)
+                } else {
+                    format!(
+                        "cargo:rustc-check-cfg=cfg({}, values({}))",
+                        name,
+                        values.join(", "),
+                    )
+                }
+            });
+            Ok(TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                build_script_output(&aliases, lines)?,
+            ))
+            .into())
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Emits a compile error unless an alias is enabled.
+///
+/// # Arguments
+///
+/// The following positional arguments are expected:
+/// 1. *alias name* - required and must be a valid [Rust identifier]
+/// 2. *error message* - required and must be a string literal
+///
+/// The alias will be expanded as `cfg(not(*))`, so its value must be a
+/// configuration predicate.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// ```
+/// attr_alias::compile_error_unless!(
+///     supported_platform,
+///     "this crate requires a Unix or Windows platform"
+/// );
+/// ```
+///
+/// [example alias file]: self#example
+/// [Rust identifier]: https://doc.rust-lang.org/reference/identifiers.html
+#[proc_macro]
+pub fn compile_error_unless(args: TokenStream) -> TokenStream {
+    let mut args = args.into_iter().peekable();
+    next!(args, Ident)
+        .and_then(|name| {
+            let _ = next!(args, Punct, as_char => ',')?;
+            let message = next!(args, Literal)?;
+            let _ = args.next_if(is_comma);
+            parse_empty(args)?;
+
+            let aliases = Aliases::get()?;
+            let conditions = aliases.resolve_args(
+                tokens!(
+                    name,
+                    Punct::new(',', Spacing::Alone),
+                    Ident::new("cfg", Span::call_site()),
+                    Group::new(
+                        Delimiter::Parenthesis,
+                        tokens!(
+                            Ident::new("not", Span::call_site()),
+                            Group::new(
+                                Delimiter::Parenthesis,
+                                TokenTree::Punct(Punct::new(
+                                    '*',
+                                    Spacing::Alone,
+                                ))
+                                .into(),
+                            ),
+                        )
+                        .collect(),
+                    ),
+                )
+                .collect(),
+                Span::call_site(),
+            )?;
+            Ok(conditions
+                .into_iter()
+                .flat_map(|condition| {
+                    tokens!(
+                        Punct::new('#', Spacing::Joint),
+                        Group::new(Delimiter::Bracket, condition),
+                    )
+                })
+                .chain(core_macro("compile_error", message))
+                .chain(aliases.create_trigger()?)
+                .collect())
         })
         .unwrap_or_else(Error::into_compile_error)
 }
@@ -304,6 +2404,19 @@ pub fn attr_alias(args: TokenStream, item: TokenStream) -> TokenStream {
 /// Equivalent to [`#[eval]`][macro@eval] but does not have restrictions on
 /// where it can be attached.
 ///
+/// The [arguments] of [`#[eval]`][macro@eval] can be specified before the
+/// tokens, followed by a comma. The tokens must then be wrapped in braces
+/// (e.g., `eval_block!(shallow, { ... })`). The `after` argument is not
+/// supported.
+///
+/// When the tokens are a single expression, rather than items or statements,
+/// they will be expanded to a block expression. Therefore, this macro can
+/// also be used where an expression is expected, such as by a `let`
+/// statement, to resolve markers within a function without wrapping the
+/// entire function. The tokens are treated as items or statements when they
+/// begin with a keyword such as `fn` or `let`, contain a `;`, or invoke a
+/// macro using braces.
+///
 /// # Examples
 ///
 /// *Compiled using the [example alias file].*
@@ -312,8 +2425,7 @@ pub fn attr_alias(args: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// ```
 /// attr_alias::eval_block! {
-///     #[attr_alias(macos, cfg_attr(*, path = "sys/macos.rs"))]
-///     #[attr_alias(macos, cfg_attr(not(*), path = "sys/common.rs"))]
+///     #[attr_alias::mod_path(macos => "sys/macos.rs", _ => "sys/common.rs")]
 ///     mod sys;
 /// }
 /// ```
@@ -326,30 +2438,77 @@ Using [`#[eval]`][macro@eval] would require a nightly feature:
 #![feature(proc_macro_hygiene)]
 
 #[attr_alias::eval]
-#[attr_alias(macos, cfg_attr(*, path = \"sys/macos.rs\"))]
-#[attr_alias(macos, cfg_attr(not(*), path = \"sys/common.rs\"))]
+#[attr_alias::mod_path(macos => \"sys/macos.rs\", _ => \"sys/common.rs\")]
 mod sys;
 ```"
 )]
 ///
+/// Macros can generate markers conditionally without causing an error:
+///
+/// ```
+/// macro_rules! function {
+///     ( $name:ident $(, $alias:ident )? ) => {
+///         attr_alias::eval_block!(allow_unused, {
+///             $( #[attr_alias($alias)] )?
+///             fn $name() {}
+///         });
+///     };
+/// }
+///
+/// function!(concurrent, macos);
+/// function!(sequential);
+/// ```
+///
+/// Expressions can be evaluated:
+///
+/// ```
+/// let platform = attr_alias::eval_block! {
+///     match () {
+///         #[attr_alias(macos)]
+///         () => "macOS",
+///         #[attr_alias(!macos)]
+///         () => "other",
+///     }
+/// };
+/// #
+/// # assert_eq!(cfg!(target_os = "macos"), platform == "macOS");
+/// ```
+///
+/// [arguments]: macro@eval#arguments
 /// [example alias file]: self#example
 #[proc_macro]
 pub fn eval_block(item: TokenStream) -> TokenStream {
-    let mut resolved = false;
-    let mut result = eval_item(item, &mut resolved)
-        .unwrap_or_else(Error::into_compile_error);
-
-    let trigger = if resolved {
-        Aliases::create_trigger()
-    } else {
-        Err(Error::new("unnecessary attribute"))
+    // Options are separated from the tokens by a comma, and the tokens must
+    // then be wrapped in braces. Items cannot end with that sequence.
+    let mut tokens: Vec<_> = item.clone().into_iter().collect();
+    let (item, options) = match &tokens[..] {
+        [.., comma, TokenTree::Group(group)]
+            if is_comma(comma) && group.delimiter() == Delimiter::Brace =>
+        {
+            let item = group.stream();
+            tokens.truncate(tokens.len() - 2);
+            let options = Options::parse(tokens.into_iter().collect())
+                .and_then(|options| {
+                    if options.after {
+                        Err(Error::new(
+                            Code::Option,
+                            "option 'after' is only supported by #[eval]",
+                        ))
+                    } else {
+                        Ok(options)
+                    }
+                });
+            (item, options)
+        }
+        _ => (item, Ok(Options::default())),
     };
-    match trigger {
-        Ok(trigger) => result.extend(trigger),
-        Err(error) => result.extend(error.into_compile_error()),
+    match options {
+        Ok(options) => {
+            let expression = is_expression(&item);
+            eval_with(item, options, expression)
+        }
+        Err(error) => error.into_compile_error(),
     }
-
-    result
 }
 
 /// Resolves [`#[attr_alias]`][macro@attr_alias] attributes.
@@ -358,6 +2517,103 @@ pub fn eval_block(item: TokenStream) -> TokenStream {
 /// [`#[attr_alias]`][macro@attr_alias] attributes within that item to be
 /// resolved without nightly features.
 ///
+/// Markers can also be wrapped in `unsafe(...)`, as required for some
+/// attributes by Rust 2024. Each expanded attribute will then be wrapped in
+/// the same way, unless it is already an unsafe attribute.
+///
+/// When multiple markers on the same item expand to identical attributes,
+/// only the first of them will be emitted. Bundles commonly share attributes
+/// such as `#[inline]`, which would otherwise cause warnings when repeated.
+/// Markers that expand to a `cfg` attribute and its negation on the same item
+/// will cause a warning, since the item would never be compiled.
+///
+/// Tokens substituted from the [alias file] are given the span of the marker
+/// that was resolved. Therefore, diagnostics for an expansion, such as those
+/// for unexpected `cfg` values, will point to the marker.
+///
+/// Within `#[doc = ...]` attributes, markers can also be written as
+/// `attr_alias!(name)`. Each will be replaced with the value of the alias as a
+/// string literal, which allows documentation to describe the same conditions
+/// as the code, using [`concat!`].
+///
+/// # Arguments
+///
+/// The following optional arguments can be specified, separated by commas:
+/// - `after` -
+///   Defer resolution until attribute macros listed after this attribute have
+///   been expanded. See [Interaction with Other Attributes] for more
+///   information.
+/// - `allow_unused` -
+///   Do not report an error when no aliases are resolved. Macros that only
+///   sometimes generate markers can use this option to wrap their output
+///   unconditionally.
+/// - `depth = N` -
+///   Only resolve aliases within `N` levels of nested delimiters. Items with
+///   large bodies or embedded languages can use this option to avoid
+///   traversing tokens that will never contain an alias. The attributes of the
+///   annotated item are always resolved.
+/// - `shallow` -
+///   Equivalent to `depth = 1`. Only the attributes of the annotated item, and
+///   of the items directly within it, will be resolved.
+/// - `skip_macros` -
+///   Do not resolve aliases within the arguments of macro invocations or the
+///   bodies of macro definitions.
+///
+/// # Bindings
+///
+/// A marker written as `#[attr_alias(let name = alias)]` binds a local name
+/// to an alias and is then removed. Markers written after it on the same
+/// item, or within the body of that item, can give the local name in place
+/// of the alias. Therefore, the alias used throughout a long `impl` block can
+/// be changed by editing a single line. When the marker is an inner
+/// attribute, the name is bound for the rest of the enclosing item instead.
+///
+/// Local names shadow aliases with the same name, and they can also be bound
+/// to other local names. They are only replaced where markers give the names
+/// of aliases, not within patterns.
+///
+/// # Nesting
+///
+/// This attribute and [`eval_block!`] can be nested within each other, as
+/// long as nested invocations are written using their full paths (e.g.,
+/// `attr_alias::eval`). The outermost invocation will resolve all aliases,
+/// including those within nested invocations, and nested invocations will be
+/// removed from its output. Therefore, only a single trigger for rebuilding
+/// when the [alias file] changes will be emitted. Options given to nested
+/// invocations are validated but have no effect.
+///
+/// # Function Bodies
+///
+/// Markers within the bodies of functions are also resolved, including those
+/// on `let` statements, expression statements, blocks, tail expressions,
+/// match arms, and struct fields. Statements and expressions are never
+/// documented, so attributes only affecting documentation (e.g., those added
+/// by the `#!doc-cfg` directive) are omitted there. When every attribute of a
+/// marker is omitted, the marker is removed entirely.
+///
+/// Markers are resolved in place, so they can only be written where Rust
+/// already accepts the resulting attributes. Without the unstable
+/// [stmt\_expr\_attributes] feature, attributes are rejected on some
+/// expressions, such as closures and the values assigned by `let`
+/// statements. Markers in those positions will cause the same error
+/// ([E0658]) as the attributes they expand to, so the enclosing statement
+/// should be annotated instead.
+///
+/// # Interaction with Other Attributes
+///
+/// Rust expands the attributes of an item in the order they are written.
+/// Attribute macros and derives listed before this attribute will see the
+/// original markers, which they must ignore or pass through. Those listed
+/// after it will only see the resolved attributes.
+///
+/// When the `after` option is given, this attribute will reemit itself
+/// directly before the first attribute that must be resolved. Attribute
+/// macros listed between the two will then be expanded first and see the
+/// original attributes. Markers cannot be placed before those macros, since
+/// Rust would try to expand them as well. The reemitted attribute uses the
+/// path `::attr_alias::eval`, so this crate cannot be renamed when using
+/// this option.
+///
 /// # Errors
 ///
 /// Errors will typically be clear, but for those that are not, they can be
@@ -368,9 +2624,10 @@ pub fn eval_block(item: TokenStream) -> TokenStream {
 /// - *"`const` items in this context need a name"* -
 ///   This attribute was attached to an item that is not at the top level of a
 ///   file.
-/// - *"non-inline modules in proc macro input are unstable"* ([E0658]) -
+/// - *"file modules in proc macro input are unstable"* ([E0658]) -
 ///   Due to the [proc\_macro\_hygiene] feature being unstable, [`eval_block!`]
-///   should be used instead.
+///   should be used instead. Unless the **nightly** feature is enabled, this
+///   attribute will also report an error suggesting it.
 ///
 /// # Examples
 ///
@@ -411,14 +2668,424 @@ pub fn eval_block(item: TokenStream) -> TokenStream {
 ```"
 )]
 ///
+/// **Applying a Lint Bundle:**
+///
+/// ```
+/// #[attr_alias::eval]
+/// #[attr_alias(strict, level = warn)]
+/// mod imp {}
+/// ```
+///
+/// **Expanding to an Entire Attribute:**
+///
+/// ```
+/// #[attr_alias::eval]
+/// #[attr_alias(value_type)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+/// #
+/// # assert_eq!(Point { x: 1, y: 2 }, Point { x: 1, y: 2 });
+/// ```
+///
+/// **Deferring Resolution:**
+///
+/// ```
+/// #[attr_alias::eval(after)]
+/// #[derive(Debug)]
+/// #[attr_alias(warnings, *)]
+/// pub struct Point {
+///     pub x: i32,
+///     pub y: i32,
+/// }
+/// ```
+///
+/// **Documenting Availability:**
+///
+/// ```
+/// #[attr_alias::eval]
+/// #[doc = concat!("Available on `", attr_alias!(macos), "`.")]
+/// pub fn name() {}
+/// ```
+///
+/// **Binding a Local Name:**
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(doc_cfg))]
+/// #
+/// use std::process::Command;
+///
+/// struct ProcessBuilder(Command);
+///
+/// #[attr_alias::eval]
+/// #[attr_alias(let platform = macos_or_windows)]
+/// impl ProcessBuilder {
+///     #[attr_alias(platform)]
+///     fn name(&mut self, name: &str) -> &mut Self {
+///         unimplemented!();
+///     }
+///
+///     #[attr_alias(platform)]
+///     fn arg0(&mut self, arg0: &str) -> &mut Self {
+///         unimplemented!();
+///     }
+/// }
+/// ```
+///
+/// **Resolving Markers within a Function:**
+///
+/// ```
+/// #[attr_alias::eval]
+/// fn platform() -> &'static str {
+///     #[attr_alias(macos)]
+///     let name = "macOS";
+///     #[attr_alias(!macos)]
+///     let name = "other";
+///     name
+/// }
+/// #
+/// # assert_eq!(cfg!(target_os = "macos"), platform() == "macOS");
+/// ```
+///
+/// **Limiting Resolution Depth:**
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(doc_cfg))]
+/// #
+/// use std::process::Command;
+///
+/// struct ProcessBuilder(Command);
+///
+/// #[attr_alias::eval(shallow)]
+/// impl ProcessBuilder {
+///     #[attr_alias(macos_or_windows)]
+///     fn name(&mut self, name: &str) -> &mut Self {
+///         unimplemented!();
+///     }
+/// }
+/// ```
+///
+/// [alias file]: self#alias-file
 /// [E0658]: https://doc.rust-lang.org/error_codes/E0658.html
 /// [example alias file]: self#example
+/// [Interaction with Other Attributes]: #interaction-with-other-attributes
 /// [proc\_macro\_hygiene]: https://doc.rust-lang.org/unstable-book/language-features/proc-macro-hygiene.html
+/// [stmt\_expr\_attributes]: https://doc.rust-lang.org/unstable-book/language-features/stmt-expr-attributes.html
 #[proc_macro_attribute]
 pub fn eval(args: TokenStream, item: TokenStream) -> TokenStream {
-    if let Err(error) = parse_empty(args) {
+    #[cfg(not(feature = "nightly"))]
+    if let Err(error) = check_inline_module(item.clone()) {
         return error.into_compile_error();
     }
 
-    eval_block(item)
+    match Options::parse(args.clone()) {
+        Ok(options) if options.after => {
+            defer(args, item).unwrap_or_else(Error::into_compile_error)
+        }
+        Ok(options) => eval_with(item, options, false),
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+/// Explains an error code.
+///
+/// Errors and warnings reported by this crate include a code, such as
+/// `AA0003`. This macro expands to a compile error containing the extended
+/// explanation for the given code, so it can be viewed without leaving the
+/// editor. The same explanations are listed below.
+///
+/// # Examples
+///
+/// ```compile_fail
+/// attr_alias::explain!(AA0003);
+/// ```
+///
+/// # Codes
+///
+/// Codes are never reused, so they can be searched for reliably.
+///
+/// ## AA0001: Unexpected Token
+///
+#[doc = include_str!("codes/AA0001.md")]
+///
+/// ## AA0002: Unknown Option
+///
+#[doc = include_str!("codes/AA0002.md")]
+///
+/// ## AA0003: Unknown Alias
+///
+#[doc = include_str!("codes/AA0003.md")]
+///
+/// ## AA0004: Invalid Lint Level
+///
+#[doc = include_str!("codes/AA0004.md")]
+///
+/// ## AA0005: Wildcard Mismatch
+///
+#[doc = include_str!("codes/AA0005.md")]
+///
+/// ## AA0006: Invalid Attribute
+///
+#[doc = include_str!("codes/AA0006.md")]
+///
+/// ## AA0007: Unreadable Alias File
+///
+#[doc = include_str!("codes/AA0007.md")]
+///
+/// ## AA0008: Invalid Alias File
+///
+#[doc = include_str!("codes/AA0008.md")]
+///
+/// ## AA0009: Alias File Changed
+///
+#[doc = include_str!("codes/AA0009.md")]
+///
+/// ## AA0010: Unnecessary Attribute
+///
+#[doc = include_str!("codes/AA0010.md")]
+///
+/// ## AA0011: Shadowed Name (Warning)
+///
+#[doc = include_str!("codes/AA0011.md")]
+///
+/// ## AA0012: Unexpected Configuration Name (Warning)
+///
+#[doc = include_str!("codes/AA0012.md")]
+///
+/// ## AA0013: Conflicting Conditions (Warning)
+///
+#[doc = include_str!("codes/AA0013.md")]
+///
+/// ## AA0014: Non-Inline Module
+///
+#[doc = include_str!("codes/AA0014.md")]
+///
+/// ## AA0015: Disallowed Configuration Name
+///
+#[doc = include_str!("codes/AA0015.md")]
+///
+/// ## AA0016: Unsettable Configuration Predicate
+///
+#[doc = include_str!("codes/AA0016.md")]
+///
+/// [alias file]: self#alias-file
+/// [proc\_macro\_hygiene]: https://doc.rust-lang.org/unstable-book/language-features/proc-macro-hygiene.html
+#[proc_macro]
+pub fn explain(args: TokenStream) -> TokenStream {
+    let mut args = args.into_iter();
+    next!(args, Ident)
+        .and_then(|code| {
+            args.next().map_or(Ok(()), |x| Err(Error::token(&x)))?;
+            Code::parse(&code.to_string()).ok_or_else(|| {
+                Error::spanned(
+                    Code::Syntax,
+                    code.span(),
+                    format!("unknown error code '{}'", code),
+                )
+            })
+        })
+        .map(|code| {
+            let message = format!("{}: {}", code.id(), code.explanation());
+            core_macro("compile_error", Literal::string(&message)).collect()
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Writes the resolved aliases to a JSON lines file for use by other tools.
+///
+/// When this macro is called by a build script, it will write
+/// "attr\_alias\_aliases.jsonl" to the directory given by `OUT_DIR`, which is
+/// within the target directory. Each line is an object with the following
+/// fields, for each alias other than "default", in the order they are
+/// defined:
+/// - "alias" - The name of the alias.
+/// - "value" - The value of the alias, with references to other aliases
+///   resolved.
+/// - "attributes" - The attributes that the alias expands to without a
+///   pattern.
+/// - "predicate" - The predicate of the first `cfg` attribute in
+///   "attributes", or `null` if there is none.
+///
+/// The build script will also run again when the [alias file] changes. This
+/// crate must be added as a build dependency:
+///
+/// ```toml
+/// [build-dependencies]
+/// attr_alias = "0.1"
+/// ```
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// In "build.rs":
+///
+/// ```ignore
+/// attr_alias::export_json!();
+/// ```
+///
+/// The file will begin with the following lines:
+///
+/// ```json
+/// {"alias":"macos","value":"target_os = \"macos\"","attributes":["cfg(target_os = \"macos\")"],"predicate":"target_os = \"macos\""}
+/// {"alias":"macos_or_windows","value":"any(target_os = \"macos\", windows)","attributes":["cfg(any(target_os = \"macos\", windows))"],"predicate":"any(target_os = \"macos\", windows)"}
+/// ```
+///
+/// [alias file]: self#alias-file
+/// [example alias file]: self#example
+#[proc_macro]
+pub fn export_json(args: TokenStream) -> TokenStream {
+    parse_empty(args)
+        .and_then(|()| {
+            let aliases = Aliases::get()?;
+            let mut lines = String::new();
+            for (name, value, attrs) in aliases.expansions()? {
+                let predicate =
+                    attrs.iter().find_map(cfg::attr_predicate).map_or_else(
+                        || "null".to_owned(),
+                        |x| json::escape(&x.to_string()),
+                    );
+                let attrs: Vec<_> = attrs
+                    .iter()
+                    .map(|x| json::escape(&x.to_string()))
+                    .collect();
+                lines += &format!(
+                    "{{\"alias\":{},\"value\":{},\"attributes\":[{}],\
+                     \"predicate\":{}}}\n",
+                    json::escape(name),
+                    json::escape(value),
+                    attrs.join(","),
+                    predicate,
+                );
+            }
+            export(&aliases, "attr_alias_aliases.jsonl", &lines)
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Writes a Markdown reference of the resolved aliases.
+///
+/// This macro is equivalent to [`export_json!`], except that it writes
+/// "attr\_alias\_aliases.md", which is intended to be published with
+/// documentation for developers. The file contains a table with a row for
+/// each alias other than "default", in the order they are defined. Each row
+/// gives the name of the alias, its value, the attributes that it expands to
+/// without a pattern, and the text given to it by the `#!doc-text`
+/// directive, if any.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// In "build.rs":
+///
+/// ```ignore
+/// attr_alias::export_markdown!();
+/// ```
+///
+/// The file will begin with the following lines:
+///
+/// ```markdown
+/// | Alias | Value | Attributes | Description |
+/// | --- | --- | --- | --- |
+/// | `macos` | `target_os = "macos"` | `#[cfg(target_os = "macos")]` |  |
+/// ```
+///
+/// [example alias file]: self#example
+#[proc_macro]
+pub fn export_markdown(args: TokenStream) -> TokenStream {
+    // Cells cannot contain line breaks, and pipes must be escaped even
+    // within code spans.
+    fn cell(text: &str) -> String {
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('|', "\\|")
+    }
+
+    parse_empty(args)
+        .and_then(|()| {
+            let aliases = Aliases::get()?;
+            let mut lines = "| Alias | Value | Attributes | Description |\n\
+                             | --- | --- | --- | --- |\n"
+                .to_owned();
+            for (name, value, attrs) in aliases.expansions()? {
+                let attrs: Vec<_> = attrs
+                    .iter()
+                    .map(|x| format!("`#[{}]`", cell(&x.to_string())))
+                    .collect();
+                lines += &format!(
+                    "| `{}` | `{}` | {} | {} |\n",
+                    name,
+                    cell(value),
+                    attrs.join(" "),
+                    aliases.description(name).map(cell).unwrap_or_default(),
+                );
+            }
+            export(&aliases, "attr_alias_aliases.md", &lines)
+        })
+        .unwrap_or_else(Error::into_compile_error)
+}
+
+/// Selects the path of a module using aliases.
+///
+/// Each argument is an arm written as `name => "path"`, where the name is
+/// that of an alias whose value is a configuration predicate. The path of the
+/// first arm whose alias is enabled will be used, and a final arm named `_`
+/// can give the path for when none of them are. This attribute expands to a
+/// `cfg_attr(..., path = "...")` attribute for each arm, whose predicate also
+/// excludes the aliases of earlier arms, so the paths are never ambiguous.
+/// Without a final `_` arm, the usual path of the module will be used when no
+/// alias is enabled.
+///
+/// Non-inline modules can only be annotated by attribute macros using the
+/// unstable [proc\_macro\_hygiene] feature. On the stable release channel,
+/// this attribute can instead be written within [`eval_block!`], which
+/// resolves it with the path `attr_alias::mod_path`.
+///
+/// # Examples
+///
+/// *Compiled using the [example alias file].*
+///
+/// ```
+/// attr_alias::eval_block! {
+///     #[attr_alias::mod_path(macos => "sys/macos.rs", _ => "sys/common.rs")]
+///     mod sys;
+/// }
+/// ```
+///
+/// It will expand to the following attributes:
+///
+/// ```ignore
+/// #[cfg_attr(target_os = "macos", path = "sys/macos.rs")]
+/// #[cfg_attr(not(target_os = "macos"), path = "sys/common.rs")]
+/// mod sys;
+/// ```
+///
+/// [example alias file]: self#example
+/// [proc\_macro\_hygiene]: https://doc.rust-lang.org/unstable-book/language-features/proc-macro-hygiene.html
+#[proc_macro_attribute]
+pub fn mod_path(args: TokenStream, item: TokenStream) -> TokenStream {
+    #[cfg(not(feature = "nightly"))]
+    if let Err(error) = check_inline_module(item.clone()) {
+        return error.into_compile_error();
+    }
+
+    Aliases::get()
+        .and_then(|aliases| {
+            let attrs = aliases.resolve_mod_path(args, Span::call_site())?;
+            Ok(attrs
+                .into_iter()
+                .flat_map(|attr| {
+                    tokens!(
+                        Punct::new('#', Spacing::Joint),
+                        Group::new(Delimiter::Bracket, attr),
+                    )
+                })
+                .chain(item)
+                .chain(aliases.create_trigger()?)
+                .collect())
+        })
+        .unwrap_or_else(Error::into_compile_error)
 }
@@ -0,0 +1,179 @@
+// These definitions do not depend on the compiler, so they are also used by
+// the command-line checker.
+
+// https://doc.rust-lang.org/rustc/check-cfg.html#well-known-names-and-values
+pub(super) const WELL_KNOWN_NAMES: &[&str] = &[
+    "clippy",
+    "debug_assertions",
+    "doc",
+    "doctest",
+    "fmt_debug",
+    "miri",
+    "overflow_checks",
+    "panic",
+    "proc_macro",
+    "relocation_model",
+    "rustfmt",
+    "sanitize",
+    "sanitizer_cfi_generalize_pointers",
+    "sanitizer_cfi_normalize_integers",
+    "target_abi",
+    "target_arch",
+    "target_endian",
+    "target_env",
+    "target_family",
+    "target_feature",
+    "target_has_atomic",
+    "target_has_atomic_equal_alignment",
+    "target_has_atomic_load_store",
+    "target_os",
+    "target_pointer_width",
+    "target_thread_local",
+    "target_vendor",
+    "test",
+    "ub_checks",
+    "unix",
+    "windows",
+];
+
+// https://doc.rust-lang.org/reference/keywords.html
+pub(super) const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const",
+    "continue", "crate", "do", "dyn", "else", "enum", "extern", "false",
+    "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "try",
+    "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+    "yield",
+];
+
+// https://doc.rust-lang.org/reference/attributes.html#built-in-attributes-index
+pub(super) const BUILTIN_ATTRS: &[&str] = &[
+    "allow",
+    "automatically_derived",
+    "cfg",
+    "cfg_attr",
+    "cold",
+    "collapse_debuginfo",
+    "crate_name",
+    "crate_type",
+    "debugger_visualizer",
+    "deny",
+    "deprecated",
+    "derive",
+    "diagnostic",
+    "doc",
+    "expect",
+    "export_name",
+    "forbid",
+    "global_allocator",
+    "ignore",
+    "inline",
+    "instruction_set",
+    "link",
+    "link_name",
+    "link_ordinal",
+    "link_section",
+    "macro_export",
+    "macro_use",
+    "must_use",
+    "naked",
+    "no_builtins",
+    "no_implicit_prelude",
+    "no_link",
+    "no_main",
+    "no_mangle",
+    "no_std",
+    "non_exhaustive",
+    "panic_handler",
+    "path",
+    "proc_macro",
+    "proc_macro_attribute",
+    "proc_macro_derive",
+    "recursion_limit",
+    "repr",
+    "should_panic",
+    "target_feature",
+    "test",
+    "track_caller",
+    "type_length_limit",
+    "unsafe",
+    "used",
+    "warn",
+    "windows_subsystem",
+];
+
+// Some names are declared by Cargo, so they are not custom names.
+pub(super) fn is_custom_name(name: &str) -> bool {
+    !["docsrs", "feature"].contains(&name) && !WELL_KNOWN_NAMES.contains(&name)
+}
+
+// Returns the names declared by a manifest, which may be incomplete, since
+// it is not parsed as TOML.
+pub(super) fn declared_names(manifest: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for declarations in manifest.split("check-cfg").skip(1) {
+        let declarations = declarations.split(']').next().unwrap_or_default();
+        for declaration in declarations.split("cfg(").skip(1) {
+            let declaration =
+                declaration.split([')', '(']).next().unwrap_or_default();
+            names.extend(
+                declaration
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|x| *x != "values" && !x.is_empty())
+                    .map(ToOwned::to_owned),
+            );
+        }
+    }
+    names
+}
+
+// Returns the features declared by the "features" table of a manifest, which
+// may also be incomplete. Each must be declared on a separate line.
+pub(super) fn feature_names(manifest: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut features = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if let Some(table) = line.strip_prefix('[') {
+            let table = table.split('#').next().unwrap_or_default();
+            features = table.trim_end().strip_suffix(']').map(str::trim)
+                == Some("features");
+            continue;
+        }
+        if !features {
+            continue;
+        }
+        let Some((key, _)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let key = key
+            .strip_prefix('"')
+            .and_then(|x| x.strip_suffix('"'))
+            .unwrap_or(key);
+        if !key.is_empty()
+            && key.chars().all(|x| x.is_alphanumeric() || "-_".contains(x))
+        {
+            names.push(key.to_owned());
+        }
+    }
+    names
+}
+
+pub(super) fn distance(string: &str, other: &str) -> usize {
+    let other: Vec<_> = other.chars().collect();
+    let mut distances: Vec<_> = (0..=other.len()).collect();
+    for (i, ch) in string.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for j in 0..other.len() {
+            let substitution = previous + usize::from(ch != other[j]);
+            previous = distances[j + 1];
+            distances[j + 1] =
+                substitution.min(previous + 1).min(distances[j] + 1);
+        }
+    }
+    distances[other.len()]
+}
@@ -0,0 +1,58 @@
+use proc_macro::TokenStream;
+use proc_macro::TokenTree;
+
+use super::Code;
+use super::Error;
+use super::Result;
+
+#[derive(Default)]
+pub(super) struct Options {
+    pub(super) after: bool,
+    pub(super) allow_unused: bool,
+    pub(super) depth: Option<usize>,
+    pub(super) skip_macros: bool,
+}
+
+impl Options {
+    pub(super) fn parse(args: TokenStream) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter().peekable();
+        while args.peek().is_some() {
+            let name = next!(args, Ident)?;
+            match &*name.to_string() {
+                "after" => options.after = true,
+                "allow_unused" => options.allow_unused = true,
+                "depth" => {
+                    let _ = next!(args, Punct, as_char => '=')?;
+                    let depth = next!(args, Literal)?;
+                    options.depth =
+                        Some(depth.to_string().parse().map_err(|_| {
+                            Error::spanned(
+                                Code::Option,
+                                depth.span(),
+                                "invalid depth".to_owned(),
+                            )
+                        })?);
+                }
+                "shallow" => options.depth = Some(1),
+                "skip_macros" => options.skip_macros = true,
+                _ => {
+                    return Err(Error::spanned(
+                        Code::Option,
+                        name.span(),
+                        format!("unknown option '{}'", name),
+                    ));
+                }
+            }
+            if args.peek().is_some() {
+                let _ = next!(args, Punct, as_char => ',')?;
+            }
+        }
+        Ok(options)
+    }
+
+    pub(super) fn skips(&self, depth: usize, macro_call: bool) -> bool {
+        self.depth.is_some_and(|x| depth >= x)
+            || (self.skip_macros && macro_call)
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+// These functions do not depend on the compiler, so they are also tested
+// directly. Windows paths are handled as strings, which allows them to be
+// tested on any platform.
+
+// Relative paths are written using "/", which is converted to the separator
+// of the platform.
+pub(super) fn join(directory: &Path, file: &str) -> PathBuf {
+    file.split('/')
+        .fold(directory.to_owned(), |path, x| path.join(x))
+}
+
+// Verbatim paths are not always supported by the include macros, so they are
+// converted to equivalent paths when possible. Other separators are then
+// replaced, since "\" would need to be escaped when the path is displayed.
+pub(super) fn normalize_windows(path: &str) -> String {
+    let path = if let Some(path) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", path)
+    } else if let Some(path) = path
+        .strip_prefix(r"\\?\")
+        .filter(|x| x.get(1..2) == Some(":"))
+    {
+        path.to_owned()
+    } else if path.starts_with(r"\\?\") {
+        // Separators are significant within other verbatim paths.
+        return path.to_owned();
+    } else {
+        path.to_owned()
+    };
+    path.replace('\\', "/")
+}
+
+// Returns the path to be written in a string literal, which will escape any
+// remaining characters, or [None] if the path is not UTF-8.
+pub(super) fn to_literal(path: &Path) -> Option<String> {
+    path.to_str().map(|x| {
+        if cfg!(windows) {
+            normalize_windows(x)
+        } else {
+            x.to_owned()
+        }
+    })
+}
+
+// Returns the path to a file in the package, relative to a source file
+// within the same package. Include macros resolve paths relative to the
+// invoking file, so this path does not need to be UTF-8.
+pub(super) fn relative(
+    package_dir: &Path,
+    source_file: &Path,
+    file: &str,
+) -> Option<String> {
+    let source_dir = source_file.parent()?.strip_prefix(package_dir).ok()?;
+    if source_dir.is_absolute()
+        || source_dir
+            .components()
+            .any(|x| !matches!(x, Component::Normal(_)))
+    {
+        return None;
+    }
+    Some("../".repeat(source_dir.components().count()) + file)
+}
@@ -0,0 +1,129 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use proc_macro::Delimiter;
+use proc_macro::Spacing;
+use proc_macro::Span;
+use proc_macro::TokenStream;
+use proc_macro::TokenTree;
+
+use super::aliases::location;
+
+const DIRECTORY: &str = "attr_alias_snapshots";
+
+struct Printer {
+    output: String,
+    depth: usize,
+    line_start: bool,
+    joint: bool,
+}
+
+impl Printer {
+    fn write(&mut self, string: &str) {
+        if self.line_start {
+            for _ in 0..self.depth {
+                self.output += "    ";
+            }
+        } else if !self.joint {
+            self.output.push(' ');
+        }
+        self.output += string;
+        self.line_start = false;
+        self.joint = false;
+    }
+
+    fn end_line(&mut self) {
+        if !self.line_start {
+            self.output.push('\n');
+            self.line_start = true;
+        }
+    }
+
+    // Attributes, statements, and the contents of braces are each printed on
+    // their own lines, so that expansions can be compared by line.
+    fn print(&mut self, tokens: TokenStream) {
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Group(group)
+                    if group.delimiter() == Delimiter::Brace =>
+                {
+                    self.write("{");
+                    self.end_line();
+                    self.depth += 1;
+                    self.print(group.stream());
+                    self.depth -= 1;
+                    self.end_line();
+                    self.write("}");
+                    self.end_line();
+                }
+                TokenTree::Punct(x) if x.as_char() == '#' => {
+                    self.end_line();
+                    self.write("#");
+                    self.joint = true;
+                    if let Some(TokenTree::Punct(x)) = tokens.peek() {
+                        if x.as_char() == '!' {
+                            self.write("!");
+                            self.joint = true;
+                            let _ = tokens.next();
+                        }
+                    }
+                    if let Some(TokenTree::Group(group)) =
+                        tokens.next_if(|x| matches!(x, TokenTree::Group(_)))
+                    {
+                        self.write(&group.to_string());
+                        self.end_line();
+                    }
+                }
+                TokenTree::Punct(x) => {
+                    if matches!(x.as_char(), ',' | ';') {
+                        self.joint = true;
+                    }
+                    self.write(&x.to_string());
+                    if x.as_char() == ';' {
+                        self.end_line();
+                    } else {
+                        self.joint = x.spacing() == Spacing::Joint;
+                    }
+                }
+                _ => self.write(&token.to_string()),
+            }
+        }
+    }
+}
+
+// Snapshots are numbered in the order of expansion. Those from earlier
+// compilations are removed first, since their invocations may no longer
+// exist. Failing to write them should not affect the build.
+pub(super) fn write(span: Span, tokens: &TokenStream) {
+    static COUNT: Mutex<usize> = Mutex::new(0);
+
+    if !env::var_os("ATTR_ALIAS_SNAPSHOT").is_some_and(|x| x == "1") {
+        return;
+    }
+    let Some(out_dir) = env::var_os("OUT_DIR") else {
+        return;
+    };
+
+    let directory = Path::new(&out_dir).join(DIRECTORY);
+    let mut count = COUNT.lock().unwrap_or_else(PoisonError::into_inner);
+    if *count == 0 {
+        let _ = fs::remove_dir_all(&directory);
+    }
+    *count += 1;
+
+    let mut printer = Printer {
+        output: format!("// {}\n", location(span)),
+        depth: 0,
+        line_start: true,
+        joint: false,
+    };
+    printer.print(tokens.clone());
+    printer.end_line();
+    let _ = fs::create_dir_all(&directory).and_then(|()| {
+        fs::write(directory.join(format!("{:04}.rs", count)), printer.output)
+    });
+}
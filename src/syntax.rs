@@ -0,0 +1,291 @@
+// The alias file is parsed by the macros and by the command-line checker, so
+// the parts that do not depend on the compiler are shared, to keep them from
+// disagreeing about which files are valid. Values of aliases are left to
+// each, since the macros parse them with the compiler.
+
+use super::lexer;
+use super::names;
+
+const DIRECTIVES: &str = "supported directives: #!allow-cfg, #!allow-lints, \
+                          #!check-cfg-names, #!doc-cfg, #!doc-text, \
+                          #!feature-aliases, #!generate-not, \
+                          #!msrv-aliases, #!platforms, #!trigger-attr, \
+                          #!trigger-prefix";
+
+pub(super) struct Error {
+    pub(super) offset: usize,
+    pub(super) message: String,
+    pub(super) note: Option<&'static str>,
+}
+
+impl Error {
+    fn new(offset: usize, message: String) -> Self {
+        Self {
+            offset,
+            message,
+            note: None,
+        }
+    }
+}
+
+impl From<lexer::Error> for Error {
+    fn from(value: lexer::Error) -> Self {
+        Self::new(value.offset, value.message)
+    }
+}
+
+// Text that may define an alias. Only text that follows "*" at the start of
+// a line is starred, and other text can only contain comments.
+pub(super) struct Chunk {
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) line: usize,
+    pub(super) starred: bool,
+}
+
+pub(super) enum Directive<'a> {
+    AllowCfg(Vec<&'a str>),
+    AllowLints,
+    CheckCfgNames,
+    DocCfg(Vec<(&'a str, usize)>),
+    DocText {
+        alias: (&'a str, usize),
+        text: &'a str,
+    },
+    FeatureAliases,
+    GenerateNot,
+    MsrvAliases(Vec<&'a str>),
+    Platforms(Vec<&'a str>),
+    TriggerAttr(&'a str),
+    TriggerPrefix(&'a str),
+}
+
+pub(super) enum Item<'a> {
+    Alias(Chunk),
+    Directive {
+        offset: usize,
+        line: usize,
+        directive: Result<Directive<'a>, Error>,
+    },
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|x: char| x.is_alphabetic() || x == '_')
+        && text.chars().all(|x| x.is_alphanumeric() || x == '_')
+}
+
+// Splits the name of an alias from the text that follows it.
+pub(super) fn split_name(text: &str) -> Option<(&str, &str)> {
+    let name = text.strip_prefix("r#").unwrap_or(text);
+    if !name.starts_with(|x: char| x.is_alphabetic() || x == '_') {
+        return None;
+    }
+    let end = name
+        .find(|x: char| !x.is_alphanumeric() && x != '_')
+        .unwrap_or(name.len());
+    Some(text.split_at(end + text.len() - name.len()))
+}
+
+pub(super) fn builtin_kind(name: &str) -> Option<&'static str> {
+    if names::WELL_KNOWN_NAMES.contains(&name) {
+        Some("configuration")
+    } else if names::BUILTIN_ATTRS.contains(&name) {
+        Some("attribute")
+    } else if names::KEYWORDS.contains(&name) {
+        Some("keyword")
+    } else {
+        None
+    }
+}
+
+// Omitted components of versions are zero, as they are for "rust-version".
+pub(super) fn parse_version(text: &str) -> Option<[u64; 3]> {
+    let mut version = [0; 3];
+    let mut components = text.split('.');
+    for component in &mut version {
+        if let Some(x) = components.next() {
+            *component = x.parse().ok()?;
+        }
+    }
+    components.next().is_none().then_some(version)
+}
+
+// Lists are separated by commas, and each item is returned with its offset.
+fn split_list(args: &str, mut offset: usize) -> Vec<(&str, usize)> {
+    let mut list = Vec::new();
+    for arg in args.split(',') {
+        let trimmed = arg.trim();
+        if !trimmed.is_empty() {
+            list.push((trimmed, offset + arg.len() - arg.trim_start().len()));
+        }
+        offset += arg.len() + 1;
+    }
+    list
+}
+
+// Arguments other than lists continue until the end of the line, so they can
+// contain commas.
+fn parse_directive(
+    source: &str,
+    offset: usize,
+    end: usize,
+) -> Result<Directive<'_>, Error> {
+    let directive = &source[offset + 2..end];
+    let (name, args) = directive
+        .split_once(char::is_whitespace)
+        .unwrap_or((directive, ""));
+    let args_offset = end - args.len();
+    let no_args = |directive| {
+        if args.trim().is_empty() {
+            Ok(directive)
+        } else {
+            Err(Error::new(
+                offset,
+                format!("directive '#!{}' does not take arguments", name),
+            ))
+        }
+    };
+    let names = || split_list(args, args_offset).into_iter().map(|(x, _)| x);
+
+    match name {
+        "allow-cfg" => Ok(Directive::AllowCfg(names().collect())),
+        "allow-lints" => no_args(Directive::AllowLints),
+        "check-cfg-names" => no_args(Directive::CheckCfgNames),
+        "doc-cfg" => Ok(Directive::DocCfg(split_list(args, args_offset))),
+        "doc-text" => {
+            let alias_offset = end - args.trim_start().len();
+            match args.trim().split_once(char::is_whitespace) {
+                Some((alias, text)) => Ok(Directive::DocText {
+                    alias: (alias, alias_offset),
+                    text: text.trim(),
+                }),
+                None => Err(Error::new(
+                    offset,
+                    "directive '#!doc-text' requires an alias name and text"
+                        .to_owned(),
+                )),
+            }
+        }
+        "feature-aliases" => no_args(Directive::FeatureAliases),
+        "generate-not" => no_args(Directive::GenerateNot),
+        "msrv-aliases" => {
+            let versions = split_list(args, args_offset);
+            for &(version, version_offset) in &versions {
+                if parse_version(version).is_none() {
+                    return Err(Error::new(
+                        version_offset,
+                        format!(
+                            "invalid version '{}' in directive \
+                             '#!msrv-aliases'",
+                            version,
+                        ),
+                    ));
+                }
+            }
+            Ok(Directive::MsrvAliases(
+                versions.into_iter().map(|(x, _)| x).collect(),
+            ))
+        }
+        "platforms" => {
+            let platforms = split_list(args, args_offset);
+            if platforms.is_empty() {
+                return Err(Error::new(
+                    offset,
+                    "directive '#!platforms' requires a list of operating \
+                     systems"
+                        .to_owned(),
+                ));
+            }
+            for &(platform, platform_offset) in &platforms {
+                if !is_identifier(platform) {
+                    return Err(Error::new(
+                        platform_offset,
+                        format!(
+                            "invalid operating system '{}' in directive \
+                             '#!platforms'",
+                            platform,
+                        ),
+                    ));
+                }
+            }
+            Ok(Directive::Platforms(
+                platforms.into_iter().map(|(x, _)| x).collect(),
+            ))
+        }
+        "trigger-attr" => {
+            if lexer::tokenize(source, args_offset, end)?.is_empty() {
+                return Err(Error::new(
+                    offset,
+                    "directive '#!trigger-attr' requires an attribute"
+                        .to_owned(),
+                ));
+            }
+            Ok(Directive::TriggerAttr(args.trim()))
+        }
+        "trigger-prefix" => {
+            if !is_identifier(args.trim()) {
+                return Err(Error::new(
+                    offset,
+                    "directive '#!trigger-prefix' requires an identifier"
+                        .to_owned(),
+                ));
+            }
+            Ok(Directive::TriggerPrefix(args.trim()))
+        }
+        _ => Err(Error {
+            note: Some(DIRECTIVES),
+            ..Error::new(offset, format!("unknown directive '#!{}'", name))
+        }),
+    }
+}
+
+// The file is scanned once, by line. Aliases begin with "*" at the start of a
+// line and continue until the next alias or directive. Directives are lines
+// that begin with "#!", and they are returned in order with the aliases, since
+// some only apply to the aliases before them.
+pub(super) fn parse(source: &str) -> Vec<Item<'_>> {
+    let mut items = Vec::new();
+    // The start, line, and whether it is an alias, for the text being
+    // scanned.
+    let mut chunk = Some((0, 1, false));
+    let mut line_start = 0_usize;
+    for (i, line) in source.split('\n').enumerate() {
+        let line_number = i + 1;
+        let trimmed = line.trim_start();
+        let is_directive = trimmed.starts_with("#!");
+        if is_directive || line.starts_with('*') {
+            if let Some((start, line, starred)) = chunk.take() {
+                items.push(Item::Alias(Chunk {
+                    start,
+                    end: line_start.saturating_sub(1).max(start),
+                    line,
+                    starred,
+                }));
+            }
+        }
+
+        if is_directive {
+            let offset = line_start + line.len() - trimmed.len();
+            let end = line_start + line.trim_end().len();
+            items.push(Item::Directive {
+                offset,
+                line: line_number,
+                directive: parse_directive(source, offset, end),
+            });
+        } else if line.starts_with('*') {
+            chunk = Some((line_start + 1, line_number, true));
+        } else if chunk.is_none() {
+            chunk = Some((line_start, line_number, false));
+        }
+        line_start += line.len() + 1;
+    }
+    if let Some((start, line, starred)) = chunk {
+        items.push(Item::Alias(Chunk {
+            start,
+            end: source.len(),
+            line,
+            starred,
+        }));
+    }
+    items
+}
@@ -0,0 +1,60 @@
+use proc_macro::Delimiter;
+use proc_macro::Group;
+use proc_macro::Literal;
+use proc_macro::Punct;
+use proc_macro::Spacing;
+use proc_macro::Span;
+use proc_macro::TokenStream;
+use proc_macro::TokenTree;
+
+// Tokens from the compiler are only valid during the invocation that
+// received them, so values are stored using this representation and
+// converted for each use.
+pub(super) enum Token {
+    Group(Delimiter, Vec<Self>),
+    Ident(String),
+    Punct(char, Spacing),
+    Literal(String),
+}
+
+impl Token {
+    pub(super) fn from_stream(stream: TokenStream) -> Vec<Self> {
+        stream
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Group(x) => {
+                    Self::Group(x.delimiter(), Self::from_stream(x.stream()))
+                }
+                TokenTree::Ident(x) => Self::Ident(x.to_string()),
+                TokenTree::Punct(x) => Self::Punct(x.as_char(), x.spacing()),
+                TokenTree::Literal(x) => Self::Literal(x.to_string()),
+            })
+            .collect()
+    }
+
+    // All tokens are given the same span, so diagnostics for them will point
+    // to where they were used.
+    pub(super) fn to_stream(tokens: &[Self], span: Span) -> TokenStream {
+        tokens
+            .iter()
+            .map(|token| {
+                let mut token: TokenTree = match token {
+                    Self::Group(delimiter, tokens) => {
+                        Group::new(*delimiter, Self::to_stream(tokens, span))
+                            .into()
+                    }
+                    Self::Ident(name) => super::ident(name, span).into(),
+                    Self::Punct(ch, spacing) => {
+                        Punct::new(*ch, *spacing).into()
+                    }
+                    Self::Literal(literal) => literal
+                        .parse::<Literal>()
+                        .expect("literal was not tokenized by the compiler")
+                        .into(),
+                };
+                token.set_span(span);
+                token
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use proc_macro::Span;
+
+use super::json::escape;
+
+const FILE: &str = "attr_alias_usage.jsonl";
+
+type Usage = BTreeMap<String, (usize, BTreeSet<String>)>;
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(attr_alias_span_file)]
+fn file(span: Span) -> Option<String> {
+    Some(span.file())
+}
+
+#[cfg(not(attr_alias_span_file))]
+fn file(_: Span) -> Option<String> {
+    None
+}
+
+// Macros cannot run code after expansion has finished, so the report is
+// rewritten after each use. It will then be complete once the compiler
+// finishes. Failing to write it should not affect the build.
+pub(super) fn record<'a, I>(defined: I, name: &str, span: Span)
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    static USAGE: Mutex<Usage> = Mutex::new(BTreeMap::new());
+
+    if !env::var_os("ATTR_ALIAS_USAGE").is_some_and(|x| x == "1") {
+        return;
+    }
+    let Some(out_dir) = env::var_os("OUT_DIR") else {
+        return;
+    };
+
+    let mut usage = USAGE.lock().unwrap_or_else(PoisonError::into_inner);
+    for name in defined {
+        let _ = usage.entry(name.to_owned()).or_default();
+    }
+    let (count, files) = usage.entry(name.to_owned()).or_default();
+    *count += 1;
+    files.extend(file(span));
+
+    let report: String = usage
+        .iter()
+        .map(|(name, (count, files))| {
+            let files: Vec<_> = files.iter().map(|x| escape(x)).collect();
+            format!(
+                "{{\"alias\":{},\"count\":{},\"files\":[{}]}}\n",
+                escape(name),
+                count,
+                files.join(","),
+            )
+        })
+        .collect();
+    let _ = fs::write(Path::new(&out_dir).join(FILE), report);
+}
@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+
+use proc_macro::Delimiter;
+use proc_macro::Group;
+use proc_macro::Ident;
+use proc_macro::Literal;
+use proc_macro::Punct;
+use proc_macro::Spacing;
+use proc_macro::Span;
+use proc_macro::TokenStream;
+use proc_macro::TokenTree;
+
+// Stable compilers cannot emit warnings for procedural macros, so each is
+// emitted as the use of a deprecated constant, which the compiler reports
+// with its note. Warnings are collected until the next trigger is emitted,
+// since every expansion depending on the alias file includes one. Spans are
+// only valid during the invocation that received them, so every invocation
+// must either take the warnings or clear them before returning.
+thread_local! {
+    static WARNINGS: RefCell<Vec<(&'static str, String, Span)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(not(feature = "nightly"))]
+pub(super) fn push(code: &'static str, message: String, span: Span) {
+    WARNINGS.with(|x| x.borrow_mut().push((code, message, span)));
+}
+
+#[cfg(not(feature = "nightly"))]
+pub(super) fn clear() {
+    WARNINGS.with(|x| x.borrow_mut().clear());
+}
+
+// Returns an item for each warning collected, which can be used wherever the
+// trigger can.
+pub(super) fn take() -> impl Iterator<Item = TokenTree> {
+    let warnings = WARNINGS.with(|x| x.take());
+    let mut result = Vec::new();
+    for (code, message, span) in warnings {
+        let name = Ident::new(code, span);
+        let constant = tokens!(
+            Punct::new('#', Spacing::Joint),
+            Group::new(
+                Delimiter::Bracket,
+                tokens!(
+                    Ident::new("deprecated", span),
+                    Group::new(
+                        Delimiter::Parenthesis,
+                        tokens!(
+                            Ident::new("note", span),
+                            Punct::new('=', Spacing::Alone),
+                            Literal::string(&message),
+                        )
+                        .collect(),
+                    ),
+                )
+                .collect(),
+            ),
+            Ident::new("const", span),
+            name.clone(),
+            Punct::new(':', Spacing::Alone),
+            Group::new(Delimiter::Parenthesis, TokenStream::new()),
+            Punct::new('=', Spacing::Alone),
+            Group::new(Delimiter::Parenthesis, TokenStream::new()),
+            Punct::new(';', Spacing::Alone),
+        );
+        result.extend(tokens!(
+            Ident::new("const", span),
+            Ident::new("_", span),
+            Punct::new(':', Spacing::Alone),
+            Group::new(Delimiter::Parenthesis, TokenStream::new()),
+            Punct::new('=', Spacing::Alone),
+            Group::new(
+                Delimiter::Brace,
+                constant.chain(tokens!(name,)).collect(),
+            ),
+            Punct::new(';', Spacing::Alone),
+        ));
+    }
+    result.into_iter()
+}
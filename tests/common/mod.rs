@@ -0,0 +1,38 @@
+// Helpers shared by the integration tests. Each test crate uses only some of
+// them.
+#![allow(dead_code)]
+
+use std::env;
+use std::process::Command;
+
+// A simple generator is used, so failures can be reproduced from the seed
+// without depending on another crate.
+pub struct Random(pub u64);
+
+impl Random {
+    pub fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+
+    pub fn choose<'a>(&mut self, values: &[&'a str]) -> &'a str {
+        values[self.next(values.len())]
+    }
+}
+
+// Returns the minor version of the compiler used by Cargo, in the same way as
+// the build script of this crate.
+pub fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split(['.', ' ']).nth(2)?.parse().ok()
+}
+
+// Locations of invocations are only known since Rust 1.88, and diagnostics
+// and snapshots include them.
+pub fn has_span_file() -> bool {
+    rustc_minor_version().is_some_and(|x| x >= 88)
+}
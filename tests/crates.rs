@@ -1,12 +1,18 @@
+use std::fs;
 use std::io;
+use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
 
-fn test(message: &str) -> io::Result<()> {
-    let dir = file!().strip_suffix(".rs").expect("missing extension");
-    let output = Command::new("cargo")
-        .args(["run", message])
-        .current_dir([dir, "/dependent"].concat())
-        .output()?;
+fn dir() -> PathBuf {
+    file!()
+        .strip_suffix(".rs")
+        .expect("missing extension")
+        .into()
+}
+
+fn run(command: &mut Command, message: &str) -> io::Result<()> {
+    let output = command.arg(message).output()?;
 
     assert_eq!(Some(0), output.status.code());
     assert_eq!(message.as_bytes(), output.stdout);
@@ -14,8 +20,432 @@ fn test(message: &str) -> io::Result<()> {
     Ok(())
 }
 
+fn test(message: &str) -> io::Result<()> {
+    run(
+        Command::new("cargo")
+            .arg("run")
+            .current_dir(dir().join("dependent")),
+        message,
+    )
+}
+
+// Directories are writable while their contents are changed.
+fn set_readonly(path: &Path, read_only: bool) -> io::Result<()> {
+    let set = || {
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_readonly(read_only);
+        fs::set_permissions(path, permissions)
+    };
+
+    if !read_only {
+        set()?;
+    }
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            set_readonly(&entry?.path(), read_only)?;
+        }
+    }
+    if read_only {
+        set()?;
+    }
+    Ok(())
+}
+
+fn copy(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "target" {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            copy(&path, &to.join(name))?;
+        } else {
+            let _ = fs::copy(&path, to.join(name))?;
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn test_simple() -> io::Result<()> {
     test("1")?;
     test("2")
 }
+
+// Resolution is tested through the testing feature, which can only be
+// enabled by a dependent package.
+#[test]
+fn test_expansion() -> io::Result<()> {
+    let output = Command::new("cargo")
+        .args(["test", "--quiet"])
+        .current_dir(dir().join("expansion"))
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout),
+    );
+    Ok(())
+}
+
+// Names defined by the build script are used by the package, and warnings
+// for names that were not declared are denied.
+#[test]
+fn test_cfg_aliases() -> io::Result<()> {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet"])
+        .current_dir(dir().join("aliased"))
+        .env("RUSTFLAGS", "-Dwarnings")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert_eq!(
+        if cfg!(unix) { "unix" } else { "other" }.as_bytes(),
+        output.stdout
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cfg_flags() -> io::Result<()> {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet"])
+        .current_dir(dir().join("flags"))
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert_eq!(
+        br#"--cfg docsrs --cfg nightly --cfg channel="beta""#,
+        &*output.stdout,
+    );
+    Ok(())
+}
+
+// Lints are not reported for items expanded from other crates, so the
+// attributes can only be seen in the expanded source. Printing it requires
+// an unstable option.
+#[test]
+fn test_allow_lints() -> io::Result<()> {
+    const LINT_ATTR: &str = "#[allow(warnings, clippy::all, \
+                             clippy::pedantic, clippy::nursery, \
+                             clippy::restriction)]";
+
+    let output = Command::new("cargo")
+        .args(["rustc", "--quiet", "--lib", "--", "-Zunpretty=expanded"])
+        .current_dir(dir().join("linted"))
+        .env("RUSTC_BOOTSTRAP", "1")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let expanded = String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert_eq!(3, expanded.matches(LINT_ATTR).count(), "{}", expanded);
+    Ok(())
+}
+
+// The checker reports errors and unused aliases without compiling the
+// package, and errors cause it to fail.
+#[cfg(feature = "cli")]
+fn check(name: &str, code: i32) -> io::Result<()> {
+    let package_dir = dir().join(name);
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-attr-alias"))
+        .args(["attr-alias".as_ref(), package_dir.as_os_str()])
+        .output()?;
+    assert_eq!(Some(code), output.status.code());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        fs::read_to_string(package_dir.join("expected/stderr.txt"))?,
+        String::from_utf8_lossy(&output.stderr),
+    );
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_check() -> io::Result<()> {
+    check("checked", 1)
+}
+
+// Names are only compared with those that are expected when no names are
+// allowed by "#!allow-cfg", so they are checked in a separate package.
+#[cfg(feature = "cli")]
+#[test]
+fn test_check_names() -> io::Result<()> {
+    check("checked_names", 0)
+}
+
+// Copies the packages to a new directory, so that they can be modified.
+fn copy_packages(name: &str, packages: &[&str]) -> io::Result<PathBuf> {
+    let packages_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    if packages_dir.exists() {
+        set_readonly(&packages_dir, false)?;
+        fs::remove_dir_all(&packages_dir)?;
+    }
+    for &package in packages {
+        let package_dir = packages_dir.join(package);
+        copy(&dir().join(package), &package_dir)?;
+
+        let manifest = package_dir.join("Cargo.toml");
+        fs::write(
+            &manifest,
+            fs::read_to_string(&manifest)?.replace(
+                "\"../../..\"",
+                &format!("'{}'", env!("CARGO_MANIFEST_DIR")),
+            ),
+        )?;
+    }
+    Ok(packages_dir)
+}
+
+// The nightly feature can only be tested when a nightly toolchain is
+// installed.
+const NIGHTLY: &str = "+nightly";
+
+fn has_nightly() -> bool {
+    Command::new("cargo")
+        .args([NIGHTLY, "--version"])
+        .output()
+        .is_ok_and(|x| x.status.success())
+}
+
+// Dependencies from registries and vendored directories are read-only, and
+// they are built from the directory of the dependent package.
+#[test]
+fn test_packaged() -> io::Result<()> {
+    let packaged_dir =
+        copy_packages("packaged", &["dependency", "dependent"])?;
+    set_readonly(&packaged_dir.join("dependency"), true)?;
+
+    run(
+        Command::new("cargo")
+            .args(["run", "--manifest-path"])
+            .arg(packaged_dir.join("dependent").join("Cargo.toml"))
+            .current_dir(&packaged_dir),
+        "1",
+    )
+}
+
+// The alias file is not a source file, so the macros must track it for
+// changes to cause the dependency to be rebuilt.
+fn test_rebuild(name: &str, toolchain: &[&str]) -> io::Result<()> {
+    let packages_dir = copy_packages(name, &["dependency", "dependent"])?;
+    let dependency = packages_dir.join("dependency");
+    let command = || {
+        let mut command = Command::new("cargo");
+        let _ = command
+            .args(toolchain)
+            .arg("run")
+            .current_dir(packages_dir.join("dependent"));
+        command
+    };
+    if !toolchain.is_empty() {
+        let manifest = dependency.join("Cargo.toml");
+        fs::write(
+            &manifest,
+            fs::read_to_string(&manifest)?.replacen(
+                "version = \"*\"",
+                "version = \"*\", features = [\"nightly\"]",
+                1,
+            ),
+        )?;
+    }
+    run(&mut command(), "a")?;
+
+    let src_dir = dependency.join("src");
+    fs::write(
+        src_dir.join("shout.rs"),
+        "pub fn print(message: &str) {\n    print!(\"{}\", \
+         message.to_uppercase());\n}\n",
+    )?;
+    let alias_file = src_dir.join("attr-aliases.txt");
+    fs::write(
+        &alias_file,
+        fs::read_to_string(&alias_file)?.replace("print.rs", "shout.rs"),
+    )?;
+    let output = command().arg("a").output()?;
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!(b"A", &*output.stdout);
+
+    Ok(())
+}
+
+#[test]
+fn test_rebuild_trigger() -> io::Result<()> {
+    test_rebuild("rebuild_trigger", &[])
+}
+
+// The nightly feature tracks the file without a trigger.
+#[test]
+fn test_rebuild_tracked_path() -> io::Result<()> {
+    if !has_nightly() {
+        return Ok(());
+    }
+    test_rebuild("rebuild_tracked_path", &[NIGHTLY])
+}
+
+#[test]
+fn test_nightly() -> io::Result<()> {
+    if !has_nightly() {
+        return Ok(());
+    }
+    let package_dir = copy_packages("nightly", &["nightly"])?.join("nightly");
+    let platform = || -> io::Result<_> {
+        let output = Command::new("cargo")
+            .args([NIGHTLY, "run", "--quiet"])
+            .current_dir(&package_dir)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr),
+        );
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let (platform_1, platform_2) = if cfg!(unix) {
+        ("unix", "other")
+    } else {
+        ("other", "unix")
+    };
+    assert_eq!(platform_1, platform()?);
+
+    let alias_file = package_dir.join("src").join("attr-aliases.txt");
+    fs::write(
+        &alias_file,
+        fs::read_to_string(&alias_file)?
+            .replace("*unix_like = unix", "*unix_like = not(unix)"),
+    )?;
+    assert_eq!(platform_2, platform()?);
+
+    Ok(())
+}
+
+// Reports are written to the directory given to the build script, and they
+// are compared to the files with the same names in the "expected" directory
+// of the package.
+fn assert_report(package_dir: &Path, file: &str) -> io::Result<()> {
+    let build_dir = package_dir.join("target").join("debug").join("build");
+    for entry in fs::read_dir(build_dir)? {
+        let path = entry?.path().join("out").join(file);
+        if path.exists() {
+            assert_eq!(
+                fs::read_to_string(package_dir.join("expected").join(file))?,
+                fs::read_to_string(path)?,
+            );
+            return Ok(());
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, file))
+}
+
+// The target directory is set, since reports are read from it.
+fn build_reports(package_dir: &Path, vars: &[(&str, &str)]) -> io::Result<()> {
+    let output = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .current_dir(package_dir)
+        .env("CARGO_TARGET_DIR", package_dir.join("target"))
+        .envs(vars.iter().copied())
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    Ok(())
+}
+
+// Lines from earlier compilations are replaced.
+#[test]
+fn test_json() -> io::Result<()> {
+    let package_dir = copy_packages("json", &["reports"])?.join("reports");
+    let vars = [("ATTR_ALIAS_JSON", "out_dir")];
+    build_reports(&package_dir, &vars)?;
+    let lib_file = package_dir.join("src").join("lib.rs");
+    fs::write(&lib_file, fs::read_to_string(&lib_file)? + "\n")?;
+    build_reports(&package_dir, &vars)?;
+
+    assert_report(&package_dir, "attr_alias.jsonl")?;
+    Ok(())
+}
+
+#[test]
+fn test_export_json() -> io::Result<()> {
+    let package_dir =
+        copy_packages("export_json", &["exported"])?.join("exported");
+    build_reports(&package_dir, &[])?;
+    assert_report(&package_dir, "attr_alias_aliases.jsonl")
+}
+
+// Pipes must be escaped within cells.
+#[test]
+fn test_export_markdown() -> io::Result<()> {
+    let package_dir =
+        copy_packages("export_markdown", &["exported"])?.join("exported");
+    build_reports(&package_dir, &[])?;
+    assert_report(&package_dir, "attr_alias_aliases.md")
+}
+
+// Names declared by the build script cannot be seen by the macros, so they
+// must not be reported.
+#[test]
+fn test_custom_cfg() -> io::Result<()> {
+    let output = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .current_dir(dir().join("custom_cfg"))
+        .env("RUSTFLAGS", "-Dwarnings")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    Ok(())
+}
+
+#[test]
+fn test_usage() -> io::Result<()> {
+    let package_dir = copy_packages("usage", &["reports"])?.join("reports");
+    build_reports(&package_dir, &[("ATTR_ALIAS_USAGE", "1")])?;
+    assert_report(&package_dir, "attr_alias_usage.jsonl")
+}
+
+// The fixture changes the alias file while it is compiled.
+#[test]
+fn test_refresh() -> io::Result<()> {
+    let build = |refresh| -> io::Result<_> {
+        let package_dir =
+            copy_packages("refresh", &["refresh"])?.join("refresh");
+        let mut command = Command::new("cargo");
+        let _ = command.args(["build", "--quiet"]).current_dir(package_dir);
+        if refresh {
+            let _ = command.env("ATTR_ALIAS_REFRESH", "1");
+        }
+        command.output()
+    };
+
+    let output = build(false)?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("[AA0009]"));
+
+    let output = build(true)?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    Ok(())
+}
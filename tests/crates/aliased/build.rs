@@ -0,0 +1,3 @@
+fn main() {
+    attr_alias::cfg_aliases!();
+}
@@ -0,0 +1,6 @@
+fn main() {
+    #[cfg(all(unix_like, unix_or_windows))]
+    print!("unix");
+    #[cfg(not(unix_like))]
+    print!("other");
+}
@@ -0,0 +1,15 @@
+//! Stands in for the published crate, which cannot be downloaded when tests
+//! are run offline. It accepts the same syntax, but predicates are evaluated
+//! for the host.
+
+#[macro_export]
+macro_rules! cfg_aliases {
+    ( $( $alias:ident : { $( $predicate:tt )+ } ),* $(,)? ) => {
+        $(
+            println!("cargo:rustc-check-cfg=cfg({})", stringify!($alias));
+            if cfg!($( $predicate )+) {
+                println!("cargo:rustc-cfg={}", stringify!($alias));
+            }
+        )*
+    };
+}
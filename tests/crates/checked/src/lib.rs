@@ -0,0 +1,19 @@
+use attr_alias::attr_alias;
+
+#[attr_alias(unix_like)]
+pub fn platform() {}
+
+#[attr_alias(documented)]
+pub fn documented() {}
+
+#[attr_alias(empty)]
+pub fn empty() {}
+
+#[attr_alias(early)]
+pub fn early() {}
+
+#[attr_alias(invalid)]
+pub fn invalid() {}
+
+#[attr_alias(android)]
+pub fn android() {}
@@ -0,0 +1,10 @@
+use attr_alias::attr_alias;
+
+#[attr_alias(typo)]
+pub fn typo() {}
+
+#[attr_alias(declared)]
+pub fn declared() {}
+
+#[attr_alias(builtin)]
+pub fn builtin() {}
@@ -0,0 +1,3 @@
+fn main() {
+    attr_alias::check_cfg!();
+}
@@ -0,0 +1,7 @@
+#[attr_alias::eval]
+#[attr_alias(nightly_docs)]
+pub fn custom() {}
+
+#[attr_alias::eval]
+#[attr_alias(!nightly_docs)]
+pub fn custom() {}
@@ -0,0 +1,8 @@
+fn main() {
+    // Otherwise, the package would be rebuilt when any of its files change,
+    // which would hide problems with tracking the alias file.
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Configuration options describe the host within build scripts.
+    assert_eq!(cfg!(unix), attr_alias::any!(unix_host));
+}
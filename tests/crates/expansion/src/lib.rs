@@ -0,0 +1,2 @@
+//! Resolution and the items generated from the alias file are tested by the
+//! integration tests of this package.
@@ -0,0 +1,14 @@
+// Each constant holds the resolved value of its alias. The default alias is
+// omitted, and raw identifiers are written without their prefix.
+attr_alias::alias_consts!();
+
+#[test]
+fn test_consts() {
+    assert_eq!(r#"target_os = "macos""#, aliases::MACOS);
+    assert_eq!(
+        r#"any(target_os = "macos", windows)"#,
+        aliases::MACOS_OR_WINDOWS,
+    );
+    assert_eq!(r#"feature = "async""#, aliases::ASYNC);
+    assert_eq!("derive(Clone, Copy); repr(u8)", aliases::VALUE_TYPE);
+}
@@ -0,0 +1,777 @@
+use attr_alias::assert_expansion;
+
+#[test]
+fn test_default() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        {
+            #[attr_alias(macos)]
+            struct Macos;
+        },
+        {
+            #[cfg(target_os = "macos")]
+            struct Macos;
+        },
+    );
+}
+
+#[test]
+fn test_no_default() {
+    assert_expansion!(
+        r#"*always_inline = inline(always)"#,
+        {
+            #[attr_alias(always_inline)]
+            fn inline() {}
+        },
+        {
+            #[inline(always)]
+            fn inline() {}
+        },
+    );
+}
+
+#[test]
+fn test_pattern() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+            *unix_like = unix
+        "#,
+        {
+            #[attr_alias(macos, unix_like, cfg_attr(all(*, *), doc = "x"))]
+            struct Both;
+        },
+        {
+            #[cfg_attr(all(target_os = "macos", unix), doc = "x")]
+            struct Both;
+        },
+    );
+}
+
+#[test]
+fn test_references() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+            *macos_or_windows = attr_alias(macos, any(*, windows))
+            *supported = attr_alias(macos_or_windows, any(*, unix))
+        "#,
+        {
+            #[attr_alias(supported)]
+            struct Supported;
+        },
+        {
+            #[cfg(any(target_os = "macos", windows, unix))]
+            struct Supported;
+        },
+    );
+}
+
+#[test]
+fn test_raw_default() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        {
+            #[attr_alias(macos, cfg_attr(docsrs, attr_alias(r#default)))]
+            struct Documented;
+        },
+        {
+            #[cfg_attr(docsrs, cfg(target_os = "macos"))]
+            struct Documented;
+        },
+    );
+}
+
+#[test]
+fn test_bundle() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *strict = deny(unsafe_code); warn(unused_results)
+            *value_type = derive(Clone, Copy, Debug);
+        "#,
+        {
+            #[attr_alias(strict)]
+            #[attr_alias(value_type)]
+            struct Value;
+        },
+        {
+            #[deny(unsafe_code)]
+            #[warn(unused_results)]
+            #[derive(Clone, Copy, Debug)]
+            struct Value;
+        },
+    );
+}
+
+#[test]
+fn test_level() {
+    assert_expansion!(
+        r#"*strict = deny(unsafe_code); warn(unused_results)"#,
+        {
+            #[attr_alias(strict, level = forbid)]
+            struct Strict;
+        },
+        {
+            #[forbid(unsafe_code)]
+            #[forbid(unused_results)]
+            struct Strict;
+        },
+    );
+}
+
+#[test]
+fn test_simplified() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *unix_like = not(not(any(unix, unix)))
+        "#,
+        {
+            #[attr_alias(unix_like)]
+            struct Simplified;
+        },
+        {
+            #[cfg(unix)]
+            struct Simplified;
+        },
+    );
+}
+
+#[test]
+fn test_nested() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        {
+            impl Outer {
+                #[attr_alias(macos)]
+                fn inner() {}
+            }
+        },
+        {
+            impl Outer {
+                #[cfg(target_os = "macos")]
+                fn inner() {}
+            }
+        },
+    );
+}
+
+#[test]
+fn test_doc_cfg() {
+    assert_expansion!(
+        r#"
+            #!doc-cfg
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        {
+            #[attr_alias(macos)]
+            struct Macos;
+        },
+        {
+            #[cfg(target_os = "macos")]
+            #[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+            struct Macos;
+        },
+    );
+}
+
+// Descriptions replace the generated attributes, except for negated aliases,
+// which they would not describe.
+#[test]
+fn test_doc_text() {
+    assert_expansion!(
+        r#"
+            #!doc-cfg
+            #!doc-text tokio_net Available on crate feature tokio-net only.
+            *default = cfg(*)
+            *tokio_net = feature = "tokio-net"
+        "#,
+        {
+            #[attr_alias(tokio_net)]
+            struct Enabled;
+
+            #[attr_alias(!tokio_net)]
+            struct Disabled;
+        },
+        {
+            #[cfg(feature = "tokio-net")]
+            #[cfg_attr(
+                docsrs,
+                doc = "\n\nAvailable on crate feature tokio-net only.\n\n"
+            )]
+            struct Enabled;
+
+            #[cfg(not(feature = "tokio-net"))]
+            #[cfg_attr(docsrs, doc(cfg(not(feature = "tokio-net"))))]
+            struct Disabled;
+        },
+    );
+}
+
+#[test]
+fn test_platforms() {
+    assert_expansion!(
+        r#"
+            #!platforms macos, linux
+            *default = cfg(*)
+        "#,
+        {
+            #[attr_alias(any_supported)]
+            struct Supported;
+
+            #[attr_alias(not_linux)]
+            struct NotLinux;
+        },
+        {
+            #[cfg(any(target_os = "macos", target_os = "linux"))]
+            struct Supported;
+
+            #[cfg(not(target_os = "linux"))]
+            struct NotLinux;
+        },
+    );
+}
+
+#[test]
+fn test_negation() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+            *not_unix = not(unix)
+        "#,
+        {
+            #[attr_alias(!macos)]
+            struct NotMacos;
+
+            #[attr_alias(not(macos), cfg_attr(*, doc = "x"))]
+            struct Pattern;
+
+            #[attr_alias(!not_unix)]
+            struct Unix;
+
+            #[attr_alias(!macos, not_unix, cfg(any(*, *)))]
+            struct Both;
+        },
+        {
+            #[cfg(not(target_os = "macos"))]
+            struct NotMacos;
+
+            #[cfg_attr(not(target_os = "macos"), doc = "x")]
+            struct Pattern;
+
+            #[cfg(unix)]
+            struct Unix;
+
+            #[cfg(any(not(target_os = "macos"), not(unix)))]
+            struct Both;
+        },
+    );
+}
+
+// Aliases are defined for the features declared by the manifest of this
+// package. Hyphens are replaced in their names.
+#[test]
+fn test_feature_aliases() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            #!feature-aliases
+        "#,
+        {
+            #[attr_alias(feat_default)]
+            struct Default;
+
+            #[attr_alias(!feat_tokio_net)]
+            struct NoNet;
+
+            #[attr_alias(feat_tokio_net, cfg_attr(*, doc = "x"))]
+            struct Documented;
+        },
+        {
+            #[cfg(feature = "default")]
+            struct Default;
+
+            #[cfg(not(feature = "tokio-net"))]
+            struct NoNet;
+
+            #[cfg_attr(feature = "tokio-net", doc = "x")]
+            struct Documented;
+        },
+    );
+}
+
+#[test]
+fn test_generate_not() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+            *not_unix = not(unix)
+            *unix_or_macos = any(unix, attr_alias(macos, *))
+            *value_type = derive(Clone, Copy);
+            #!platforms linux
+            #!generate-not
+            *not_value_type = derive(Debug);
+        "#,
+        {
+            #[attr_alias(not_macos)]
+            struct NotMacos;
+
+            #[attr_alias(not_linux)]
+            struct NotLinux;
+
+            #[attr_alias(not_any_supported)]
+            struct Unsupported;
+
+            #[attr_alias(not_value_type)]
+            struct Kept;
+        },
+        {
+            #[cfg(not(target_os = "macos"))]
+            struct NotMacos;
+
+            #[cfg(not(target_os = "linux"))]
+            struct NotLinux;
+
+            #[cfg(not(target_os = "linux"))]
+            struct Unsupported;
+
+            #[derive(Debug)]
+            struct Kept;
+        },
+    );
+}
+
+// The minimum supported version is declared by the manifest of this package.
+#[test]
+fn test_msrv_aliases() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            #!msrv-aliases 1.65, 1.70.0, 1.80
+        "#,
+        {
+            #[attr_alias(since_1_65)]
+            struct Older;
+
+            #[attr_alias(since_1_70_0)]
+            struct Same;
+
+            #[attr_alias(since_1_80)]
+            struct Newer;
+
+            #[attr_alias(msrv, doc = *)]
+            struct Documented;
+        },
+        {
+            #[cfg(all())]
+            struct Older;
+
+            #[cfg(all())]
+            struct Same;
+
+            #[cfg(any())]
+            struct Newer;
+
+            #[doc = "1.70"]
+            struct Documented;
+        },
+    );
+}
+
+#[test]
+fn test_mod_path() {
+    assert_expansion!(
+        r#"
+            *macos = target_os = "macos"
+            *windows_os = target_os = "windows"
+        "#,
+        {
+            #[attr_alias::mod_path(
+                macos => "sys/macos.rs",
+                windows_os => "sys/windows.rs",
+                _ => "sys/common.rs",
+            )]
+            mod sys;
+        },
+        {
+            #[cfg_attr(target_os = "macos", path = "sys/macos.rs")]
+            #[cfg_attr(
+                all(target_os = "windows", not(target_os = "macos")),
+                path = "sys/windows.rs"
+            )]
+            #[cfg_attr(
+                not(any(target_os = "macos", target_os = "windows")),
+                path = "sys/common.rs"
+            )]
+            mod sys;
+        },
+    );
+}
+
+#[test]
+fn test_binding() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+            *linux = target_os = "linux"
+        "#,
+        {
+            #[attr_alias(let target = macos)]
+            impl Process {
+                #[attr_alias(target)]
+                fn spawn() {}
+
+                #[attr_alias(!target, doc(cfg(*)))]
+                fn wait() {}
+            }
+
+            #[attr_alias(let target = linux)]
+            #[attr_alias(let other = target)]
+            mod sys {
+                #![attr_alias(let target = macos)]
+
+                #[attr_alias(target, other, cfg(any(*, *)))]
+                fn spawn() {}
+            }
+        },
+        {
+            impl Process {
+                #[cfg(target_os = "macos")]
+                fn spawn() {}
+
+                #[doc(cfg(not(target_os = "macos")))]
+                fn wait() {}
+            }
+
+            mod sys {
+                #[cfg(any(target_os = "macos", target_os = "linux"))]
+                fn spawn() {}
+            }
+        },
+    );
+}
+
+#[test]
+fn test_nested_expression() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        {
+            fn value() -> u8 {
+                let value = attr_alias::eval_block!(match () {
+                    #[attr_alias(macos)]
+                    () => 1,
+                    _ => 0,
+                });
+                attr_alias::eval_block!(
+                    #[attr_alias(macos)]
+                    let value = 2;
+                );
+                value
+            }
+        },
+        {
+            fn value() -> u8 {
+                let value = {
+                    match () {
+                        #[cfg(target_os = "macos")]
+                        () => 1,
+                        _ => 0,
+                    }
+                };
+                #[cfg(target_os = "macos")]
+                let value = 2;
+                value
+            }
+        },
+    );
+}
+
+#[test]
+fn test_function_body() {
+    assert_expansion!(
+        r#"
+            #!doc-cfg
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        {
+            #[attr_alias(macos)]
+            fn value() -> u8 {
+                #[attr_alias(macos)]
+                let value = 1;
+                #[attr_alias(!macos)]
+                {
+                    println!("other");
+                }
+                #[attr_alias(macos, doc(cfg(*)))]
+                drop(value);
+                match value {
+                    #[attr_alias(macos)]
+                    1 => value,
+                    _ => 0,
+                }
+            }
+
+            impl Process {
+                #[attr_alias(macos)]
+                fn spawn() {
+                    #[attr_alias(macos)]
+                    let _ = 1;
+                }
+            }
+        },
+        {
+            #[cfg(target_os = "macos")]
+            #[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+            fn value() -> u8 {
+                #[cfg(target_os = "macos")]
+                let value = 1;
+                #[cfg(not(target_os = "macos"))]
+                {
+                    println!("other");
+                }
+                drop(value);
+                match value {
+                    #[cfg(target_os = "macos")]
+                    1 => value,
+                    _ => 0,
+                }
+            }
+
+            impl Process {
+                #[cfg(target_os = "macos")]
+                #[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+                fn spawn() {
+                    #[cfg(target_os = "macos")]
+                    let _ = 1;
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn test_shallow() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        shallow,
+        {
+            #[attr_alias(macos)]
+            mod outer {
+                #[attr_alias(macos)]
+                struct Outer;
+
+                mod inner {
+                    #[attr_alias(macos)]
+                    struct Inner;
+                }
+            }
+        },
+        {
+            #[cfg(target_os = "macos")]
+            mod outer {
+                #[cfg(target_os = "macos")]
+                struct Outer;
+
+                mod inner {
+                    #[attr_alias(macos)]
+                    struct Inner;
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn test_depth() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        depth = 2,
+        {
+            mod first {
+                #[attr_alias(macos)]
+                struct First;
+
+                mod second {
+                    #[attr_alias(macos)]
+                    struct Second;
+
+                    mod third {
+                        #[attr_alias(macos)]
+                        struct Third;
+                    }
+                }
+            }
+        },
+        {
+            mod first {
+                #[cfg(target_os = "macos")]
+                struct First;
+
+                mod second {
+                    #[cfg(target_os = "macos")]
+                    struct Second;
+
+                    mod third {
+                        #[attr_alias(macos)]
+                        struct Third;
+                    }
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn test_skip_macros() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+        "#,
+        skip_macros,
+        {
+            #[attr_alias(macos)]
+            mod macros {
+                macro_rules! generate {
+                    () => {
+                        #[attr_alias(macos)]
+                        struct Generated;
+                    };
+                }
+
+                generate! {
+                    #[attr_alias(macos)]
+                    struct Argument;
+                }
+
+                #[attr_alias(macos)]
+                struct Resolved;
+            }
+        },
+        {
+            #[cfg(target_os = "macos")]
+            mod macros {
+                macro_rules! generate {
+                    () => {
+                        #[attr_alias(macos)]
+                        struct Generated;
+                    };
+                }
+
+                generate! {
+                    #[attr_alias(macos)]
+                    struct Argument;
+                }
+
+                #[cfg(target_os = "macos")]
+                struct Resolved;
+            }
+        },
+    );
+}
+
+#[test]
+fn test_entire_attribute() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *camel_case = serde(rename_all = "camelCase");
+            *value_type = derive(Clone, Copy, Debug);
+        "#,
+        {
+            #[attr_alias(value_type)]
+            #[attr_alias(camel_case)]
+            struct Point;
+        },
+        {
+            #[derive(Clone, Copy, Debug)]
+            #[serde(rename_all = "camelCase")]
+            struct Point;
+        },
+    );
+}
+
+#[test]
+fn test_duplicate_attributes() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+            *apple = target_os = "macos"
+            *strict = deny(unsafe_code); warn(unused_results);
+        "#,
+        {
+            #[attr_alias(macos)]
+            #[attr_alias(apple)]
+            #[attr_alias(strict)]
+            #[attr_alias(strict, level = deny)]
+            struct Duplicate;
+
+            #[attr_alias(macos)]
+            struct Other;
+        },
+        {
+            #[cfg(target_os = "macos")]
+            #[deny(unsafe_code)]
+            #[warn(unused_results)]
+            #[deny(unused_results)]
+            struct Duplicate;
+
+            #[cfg(target_os = "macos")]
+            struct Other;
+        },
+    );
+}
+
+#[test]
+fn test_multiple_patterns() {
+    assert_expansion!(
+        r#"
+            *default = cfg(*)
+            *macos = target_os = "macos"
+            *unix_like = unix
+        "#,
+        {
+            #[attr_alias(macos, cfg(*); doc(cfg(*)))]
+            struct Macos;
+
+            #[attr_alias(macos, unix_like, cfg(any(*, *)); cfg(all(*, *)))]
+            struct Both;
+        },
+        {
+            #[cfg(target_os = "macos")]
+            #[doc(cfg(target_os = "macos"))]
+            struct Macos;
+
+            #[cfg(any(target_os = "macos", unix))]
+            #[cfg(all(target_os = "macos", unix))]
+            struct Both;
+        },
+    );
+}
@@ -0,0 +1,5 @@
+// Exported files are compared with those in the "expected" directory.
+fn main() {
+    attr_alias::export_json!();
+    attr_alias::export_markdown!();
+}
@@ -0,0 +1,9 @@
+#[attr_alias::eval]
+#[attr_alias(macos_or_windows)]
+pub fn platform() {}
+
+#[attr_alias::eval]
+#[attr_alias(value_type)]
+pub enum Level {
+    Low,
+}
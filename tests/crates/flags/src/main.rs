@@ -0,0 +1,4 @@
+// Options required by multiple aliases are only given once.
+fn main() {
+    print!("{}", attr_alias::cfg_flags!(nightly_docs, beta));
+}
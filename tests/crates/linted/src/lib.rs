@@ -0,0 +1,6 @@
+// The module and both triggers are helper items.
+attr_alias::alias_consts!();
+
+#[attr_alias::eval]
+#[attr_alias(unix_like)]
+pub fn platform() {}
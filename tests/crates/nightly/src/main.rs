@@ -0,0 +1,18 @@
+use attr_alias::attr_alias;
+
+#[attr_alias(value_type)]
+struct Platform(&'static str);
+
+impl Platform {
+    #[attr_alias(unix_like)]
+    const CURRENT: Self = Self("unix");
+
+    #[attr_alias(unix_like, cfg(not(*)))]
+    const CURRENT: Self = Self("other");
+}
+
+fn main() {
+    let platform = Platform::CURRENT;
+    assert_eq!(platform, platform.clone());
+    print!("{}", platform.0);
+}
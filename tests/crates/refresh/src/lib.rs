@@ -0,0 +1,10 @@
+#[attr_alias::eval]
+#[attr_alias(unix_like)]
+pub struct Before;
+
+rewrite::append_alias!(*windows_like = windows);
+
+// This alias is only defined by the new contents.
+#[attr_alias::eval]
+#[attr_alias(windows_like)]
+pub struct After;
@@ -0,0 +1,11 @@
+#[attr_alias::eval]
+#[attr_alias(unix_like)]
+pub fn platform() -> &'static str {
+    "unix"
+}
+
+#[attr_alias::eval]
+#[attr_alias(windows)]
+pub fn platform() -> &'static str {
+    "windows"
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+
+// Macros cannot know the edition of the crate invoking them, so the same
+// aliases are expanded in each one. Each edition is only tested by compilers
+// that support it, given by their minor version.
+const EDITIONS: &[(&str, u32)] =
+    &[("2015", 0), ("2018", 31), ("2021", 56), ("2024", 85)];
+
+// Syntax that was added later is appended to the source from these files,
+// with the first edition and minor version of the compiler that accept it.
+const SYNTAX_FILES: &[(&str, &str, u32)] = &[
+    ("unsafe_attributes.rs", "2015", 82),
+    ("2021.rs", "2021", 83),
+    ("2024.rs", "2024", 85),
+];
+
+const ALIAS_FILE: &str = "attr-aliases.txt";
+
+fn test(edition: &str) -> io::Result<()> {
+    let version = common::rustc_minor_version().unwrap_or(u32::MAX);
+    if EDITIONS
+        .iter()
+        .any(|&(x, since)| x == edition && since > version)
+    {
+        return Ok(());
+    }
+
+    let editions_dir = Path::new(file!()).with_extension("").canonicalize()?;
+    let tmp_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("editions");
+    let package_dir = tmp_dir.join(edition);
+    let src_dir = package_dir.join("src");
+    fs::create_dir_all(&src_dir)?;
+    fs::write(
+        package_dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"edition_{}\"\nversion = \"0.0.0\"\nedition \
+             = \"{}\"\npublish = false\n\n[dependencies]\nattr_alias = {{ \
+             path = '{}' }}\n\n[workspace]\n",
+            edition,
+            edition,
+            env!("CARGO_MANIFEST_DIR"),
+        ),
+    )?;
+
+    let mut source = fs::read_to_string(editions_dir.join("lib.rs"))?;
+    for &(file, since_edition, since) in SYNTAX_FILES {
+        if since_edition <= edition && since <= version {
+            source += &fs::read_to_string(editions_dir.join(file))?;
+        }
+    }
+    fs::write(src_dir.join("lib.rs"), source)?;
+    let _ = fs::copy(editions_dir.join(ALIAS_FILE), src_dir.join(ALIAS_FILE))?;
+
+    // Warnings are also checked, since keywords would be reported as unused
+    // or misused names if they were not written as raw identifiers.
+    let output = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .current_dir(&package_dir)
+        .env("CARGO_TARGET_DIR", tmp_dir.join("target"))
+        .env("RUSTFLAGS", "-Dwarnings")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "edition {}:\n{}",
+        edition,
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_2015() -> io::Result<()> {
+    test("2015")
+}
+
+#[test]
+fn test_2018() -> io::Result<()> {
+    test("2018")
+}
+
+#[test]
+fn test_2021() -> io::Result<()> {
+    test("2021")
+}
+
+#[test]
+fn test_2024() -> io::Result<()> {
+    test("2024")
+}
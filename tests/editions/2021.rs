@@ -0,0 +1,13 @@
+
+attr_alias::eval_block! {
+    #[attr_alias(unix_like)]
+    pub const C_STRING: &core::ffi::CStr = c"c string";
+
+    #[attr_alias(unix_like)]
+    pub const RAW_C_STRING: &core::ffi::CStr = cr#"raw "c" string"#;
+
+    #[attr_alias(unix_like)]
+    pub fn raw_lifetime<'r#async>(value: &'r#async str) -> &'r#async str {
+        value
+    }
+}
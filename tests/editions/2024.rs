@@ -0,0 +1,7 @@
+
+attr_alias::eval_block! {
+    #[attr_alias(unix_like)]
+    unsafe extern "C" {
+        pub safe fn abs(value: i32) -> i32;
+    }
+}
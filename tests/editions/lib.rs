@@ -0,0 +1,35 @@
+#![allow(non_camel_case_types)]
+#![allow(non_upper_case_globals)]
+
+attr_alias::eval_block! {
+    #[attr_alias(value_type)]
+    pub struct r#async {
+        pub r#dyn: u8,
+        pub r#try: u8,
+        pub r#gen: u8,
+    }
+
+    #[attr_alias(r#async)]
+    pub fn r#match() -> u8 {
+        let r#yield = 1;
+        r#yield
+    }
+
+    #[attr_alias(unix_like)]
+    pub mod r#impl {
+        pub const r#loop: &str = r"raw";
+        pub const BYTES: &[u8] = br#"raw"#;
+    }
+}
+
+pub mod macros {
+    attr_alias::alias_macros!();
+
+    r#async! {
+        pub struct Async;
+    }
+
+    r#gen! {
+        pub struct Gen;
+    }
+}
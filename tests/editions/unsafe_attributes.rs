@@ -0,0 +1,13 @@
+
+attr_alias::eval_block! {
+    #[attr_alias(exported)]
+    pub extern "C" fn attr_alias_exported() {}
+
+    // Rust 2024 rejects these attributes unless they are unsafe, and earlier
+    // editions accept the wrapper, so each expansion must be wrapped once.
+    #[unsafe(attr_alias(unmangled))]
+    pub extern "C" fn attr_alias_unmangled() {}
+
+    #[unsafe(attr_alias(exported))]
+    pub extern "C" fn attr_alias_wrapped() {}
+}
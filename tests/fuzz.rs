@@ -0,0 +1,133 @@
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+use common::Random;
+
+// Tables are mutated from these lines, so most of them are close to valid
+// and reach more of the parser than random text would.
+const SEEDS: &[&str] = &[
+    "*default = cfg(*)",
+    "*macos = target_os = \"macos\"",
+    "*a = attr_alias(macos, any(*, windows))",
+    "*b = attr_alias(a, cfg_attr(*, doc = \"b\"))",
+    "*strict = deny(unsafe_code); warn(unused_results);",
+    "*r#type = doc = \"type\"",
+    "#!allow-cfg custom",
+    "#!allow-lints",
+    "#!doc-cfg macos, a",
+    "#!doc-text macos Only on macOS.",
+    "#!generate-not",
+    "#!msrv-aliases 1.70, 1.80",
+    "#!platforms macos, linux",
+    "#!trigger-attr doc(hidden)",
+    "#!trigger-prefix attr_alias_",
+    "// comment",
+    "/* block /* nested */ comment */",
+];
+
+// Tokens that change how lines are split or lexed are inserted most often.
+const FRAGMENTS: &[&str] = &[
+    "*", "=", "(", ")", ",", ";", "#!", "\"", "'", "r#", "/*", "*/", "//",
+    "\n", " ", "\t", "\r", "a", "_", "1.", "é", "\u{200b}", "🦀",
+];
+
+const ITEMS: &str = "
+    #[attr_alias(macos)]
+    struct Macos;
+
+    #[attr_alias(a, cfg_attr(*, doc = \"a\"))]
+    struct A;
+
+    #[attr_alias(b)]
+    #[attr_alias(strict, level = forbid)]
+    fn b() {}
+
+    #[attr_alias(r#type)]
+    #[attr_alias(any_supported)]
+    struct Type;
+";
+
+const CASES: usize = 500;
+
+fn mutate(random: &mut Random) -> String {
+    let mut table: Vec<char> = (0..random.next(SEEDS.len()) + 1)
+        .flat_map(|_| random.choose(SEEDS).chars().chain(Some('\n')))
+        .collect();
+    for _ in 0..random.next(8) {
+        let index = random.next(table.len() + 1);
+        match random.next(3) {
+            0 if index < table.len() => {
+                let end = (index + random.next(4) + 1).min(table.len());
+                let _ = table.drain(index..end);
+            }
+            1 if index < table.len() => {
+                let end = (index + random.next(8) + 1).min(table.len());
+                let copy: Vec<_> = table[index..end].to_vec();
+                let _ = table.splice(index..index, copy);
+            }
+            _ => {
+                let _ = table
+                    .splice(index..index, random.choose(FRAGMENTS).chars());
+            }
+        }
+    }
+    table.into_iter().collect()
+}
+
+// Each case is expanded by a separate invocation within a single package,
+// so errors are expected, but the macros should never panic.
+#[test]
+fn test_parser() -> io::Result<()> {
+    let seed = env::var("ATTR_ALIAS_FUZZ_SEED")
+        .ok()
+        .map(|x| x.parse().expect("invalid seed"))
+        .unwrap_or(0x2545_f491_4f6c_dd1d);
+    let mut random = Random(seed);
+
+    let mut source = String::new();
+    for i in 0..CASES {
+        writeln!(
+            source,
+            "pub fn case_{}() {{\nattr_alias::assert_expansion!({:?}, {{{}}}, \
+             {{}});\n}}",
+            i,
+            mutate(&mut random),
+            ITEMS,
+        )
+        .expect("writing to a string failed");
+    }
+
+    let package_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("fuzz");
+    let src_dir = package_dir.join("src");
+    fs::create_dir_all(&src_dir)?;
+    fs::write(
+        package_dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"fuzz\"\nversion = \"0.0.0\"\nedition = \
+             \"2021\"\nrust-version = \"1.70\"\npublish = \
+             false\n\n[dependencies]\nattr_alias = {{ path = '{}', features \
+             = [\"testing\"] }}\n\n[workspace]\n",
+            env!("CARGO_MANIFEST_DIR"),
+        ),
+    )?;
+    fs::write(src_dir.join("lib.rs"), source)?;
+
+    let output = Command::new("cargo")
+        .args(["check", "--quiet"])
+        .current_dir(&package_dir)
+        .output()?;
+    // Only the diagnostics for panics are displayed.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let panics: Vec<_> = stderr
+        .split("\n\n")
+        .filter(|x| x.contains("panicked") || x.contains("internal compiler"))
+        .collect();
+    assert!(panics.is_empty(), "seed {}:\n{}", seed, panics.join("\n\n"));
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::path::Path;
+
+#[path = "../src/path.rs"]
+mod path;
+
+const FILE: &str = "src/attr-aliases.txt";
+
+#[test]
+fn test_join() {
+    assert_eq!(
+        Path::new("package").join("src").join("attr-aliases.txt"),
+        path::join(Path::new("package"), FILE),
+    );
+}
+
+#[test]
+fn test_normalize_windows() {
+    for (expected, path) in [
+        (
+            "C:/package/src/attr-aliases.txt",
+            r"\\?\C:\package\src\attr-aliases.txt",
+        ),
+        (
+            "//server/share/package/src/attr-aliases.txt",
+            r"\\?\UNC\server\share\package\src\attr-aliases.txt",
+        ),
+        (
+            r"\\?\Volume{0}\package\src\attr-aliases.txt",
+            r"\\?\Volume{0}\package\src\attr-aliases.txt",
+        ),
+        (
+            "C:/package/src/attr-aliases.txt",
+            r"C:\package\src/attr-aliases.txt",
+        ),
+        (
+            "//server/share/src/attr-aliases.txt",
+            r"\\server\share\src\attr-aliases.txt",
+        ),
+    ] {
+        assert_eq!(expected, path::normalize_windows(path));
+    }
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_to_literal() {
+    // Backslashes are normal characters on other platforms.
+    for path in [r"/package\0/src/attr-aliases.txt", r"\\?\C:\package"] {
+        assert_eq!(Some(path), path::to_literal(Path::new(path)).as_deref());
+    }
+}
+
+#[cfg(windows)]
+#[test]
+fn test_to_literal() {
+    assert_eq!(
+        Some("C:/package/src/attr-aliases.txt"),
+        path::to_literal(Path::new(r"\\?\C:\package\src\attr-aliases.txt"))
+            .as_deref(),
+    );
+}
+
+#[test]
+fn test_relative() {
+    let package_dir = Path::new("package");
+    for (expected, source_file) in [
+        (Some("../src/attr-aliases.txt"), "package/src/lib.rs"),
+        (
+            Some("../../src/attr-aliases.txt"),
+            "package/examples/example/main.rs",
+        ),
+        (Some("src/attr-aliases.txt"), "package/build.rs"),
+        (None, "other/src/lib.rs"),
+        (None, "package/../other/lib.rs"),
+    ] {
+        assert_eq!(
+            expected,
+            path::relative(package_dir, Path::new(source_file), FILE)
+                .as_deref(),
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+use common::Random;
+
+const OPTIONS: &[&str] = &[
+    "unix",
+    "windows",
+    "test",
+    "debug_assertions",
+    "target_os = \"macos\"",
+    "target_os = \"linux\"",
+    "target_pointer_width = \"64\"",
+    "panic = \"unwind\"",
+];
+
+const CASES: usize = 200;
+
+// Predicates are modeled separately from the macros, so that expansions can
+// be predicted.
+#[derive(Clone)]
+enum Predicate {
+    Option(&'static str),
+    Wildcard,
+    Operator(&'static str, Vec<Self>),
+}
+
+impl Predicate {
+    fn generate(random: &mut Random, depth: usize, wildcards: usize) -> Self {
+        if wildcards == 0 && (depth == 0 || random.next(3) == 0) {
+            return Self::Option(OPTIONS[random.next(OPTIONS.len())]);
+        }
+        if wildcards == 1 && (depth == 0 || random.next(3) == 0) {
+            return Self::Wildcard;
+        }
+        if wildcards < 2 && random.next(4) == 0 {
+            return Self::Operator(
+                "not",
+                vec![Self::generate(
+                    random,
+                    depth.saturating_sub(1),
+                    wildcards,
+                )],
+            );
+        }
+
+        let operator = if random.next(2) == 0 { "any" } else { "all" };
+        let count = wildcards.max(1) + random.next(3);
+        let mut remaining = wildcards;
+        let operands = (0..count)
+            .map(|i| {
+                // Wildcards are distributed among the operands, with the
+                // last receiving those that remain.
+                let wildcards = if i + 1 == count {
+                    remaining
+                } else {
+                    random.next(remaining.min(1) + 1)
+                };
+                remaining -= wildcards;
+                Self::generate(random, depth.saturating_sub(1), wildcards)
+            })
+            .collect();
+        Self::Operator(operator, operands)
+    }
+
+    fn substitute(&self, values: &mut dyn Iterator<Item = Self>) -> Self {
+        match self {
+            Self::Option(_) => self.clone(),
+            Self::Wildcard => values.next().expect("missing value"),
+            Self::Operator(operator, operands) => Self::Operator(
+                operator,
+                operands.iter().map(|x| x.substitute(values)).collect(),
+            ),
+        }
+    }
+
+    // This matches the simplification performed when attributes are
+    // expanded.
+    fn simplify(self) -> Self {
+        let Self::Operator(operator, operands) = self else {
+            return self;
+        };
+        let mut operands = operands.into_iter().map(Self::simplify);
+        if operator == "not" {
+            return match operands.next().expect("missing operand") {
+                Self::Operator("not", mut operands) => {
+                    operands.pop().expect("missing operand")
+                }
+                operand => Self::Operator(operator, vec![operand]),
+            };
+        }
+
+        let mut simplified: Vec<Self> = Vec::new();
+        for operand in operands {
+            let nested = match operand {
+                Self::Operator(x, operands) if x == operator => operands,
+                operand => vec![operand],
+            };
+            for operand in nested {
+                if simplified.iter().all(|x| x.text() != operand.text()) {
+                    simplified.push(operand);
+                }
+            }
+        }
+        if simplified.len() == 1 {
+            return simplified.pop().expect("missing operand");
+        }
+        Self::Operator(operator, simplified)
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Self::Option(option) => (*option).to_owned(),
+            Self::Wildcard => "*".to_owned(),
+            Self::Operator(operator, operands) => format!(
+                "{}({})",
+                operator,
+                operands
+                    .iter()
+                    .map(Self::text)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        }
+    }
+}
+
+struct Case {
+    // Definitions with references, as they would be written.
+    table: String,
+    // Definitions replaced by their printed and reparsed values.
+    printed_table: String,
+    input: String,
+    expected: String,
+}
+
+fn generate(random: &mut Random) -> Case {
+    let mut table = "*default = cfg(*)\n".to_owned();
+    let mut printed_table = table.clone();
+    let mut values: Vec<Predicate> = Vec::new();
+    for i in 0..random.next(4) + 1 {
+        let value = if i != 0 && random.next(2) == 0 {
+            let reference = random.next(i);
+            let pattern = Predicate::generate(random, 2, 1);
+            writeln!(
+                table,
+                "*a{} = attr_alias(a{}, {})",
+                i,
+                reference,
+                pattern.text(),
+            )
+            .expect("writing to a string failed");
+            pattern
+                .substitute(&mut Some(values[reference].clone()).into_iter())
+        } else {
+            let value = Predicate::generate(random, 3, 0);
+            writeln!(table, "*a{} = {}", i, value.text())
+                .expect("writing to a string failed");
+            value
+        };
+        let value = value.simplify();
+        writeln!(printed_table, "*a{} = {}", i, value.text())
+            .expect("writing to a string failed");
+        values.push(value);
+    }
+
+    // Patterns are given wildcards for each alias, and they are wrapped in
+    // another attribute, so that tokens around them are also checked.
+    let names: Vec<_> = (0..random.next(2) + 1)
+        .map(|_| random.next(values.len()))
+        .collect();
+    let pattern = Predicate::generate(random, 2, names.len());
+    let predicate = pattern
+        .substitute(&mut names.iter().map(|&x| values[x].clone()))
+        .simplify();
+    let names: Vec<_> = names.iter().map(|x| format!("a{}", x)).collect();
+    Case {
+        table,
+        printed_table,
+        input: format!(
+            "#[attr_alias({}, cfg_attr({}, doc = \"x\"))] struct A;",
+            names.join(", "),
+            pattern.text(),
+        ),
+        expected: format!(
+            "#[cfg_attr({}, doc = \"x\")] struct A;",
+            predicate.text(),
+        ),
+    }
+}
+
+// Each case checks that wildcards are substituted without losing tokens,
+// that printing and parsing resolved values does not change the expansion,
+// and that expanding the result again does not change it.
+#[test]
+fn test_resolution() -> io::Result<()> {
+    let seed = env::var("ATTR_ALIAS_PROPERTY_SEED")
+        .ok()
+        .map(|x| x.parse().expect("invalid seed"))
+        .unwrap_or(0x9e37_79b9_7f4a_7c15);
+    let mut random = Random(seed);
+
+    let mut source = String::new();
+    for i in 0..CASES {
+        let case = generate(&mut random);
+        for (j, (table, input)) in [
+            (&case.table, &case.input),
+            (&case.printed_table, &case.input),
+            (&case.table, &case.expected),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            writeln!(
+                source,
+                "#[test]\nfn case_{}_{}() {{\nattr_alias::assert_expansion!(\
+                 {:?}, {{{}}}, {{{}}});\n}}",
+                i, j, table, input, case.expected,
+            )
+            .expect("writing to a string failed");
+        }
+    }
+
+    let package_dir =
+        Path::new(env!("CARGO_TARGET_TMPDIR")).join("properties");
+    let src_dir = package_dir.join("src");
+    fs::create_dir_all(&src_dir)?;
+    fs::write(
+        package_dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"properties\"\nversion = \"0.0.0\"\nedition \
+             = \"2021\"\npublish = false\n\n[dependencies]\nattr_alias = {{ \
+             path = '{}', features = [\"testing\"] }}\n\n[workspace]\n",
+            env!("CARGO_MANIFEST_DIR"),
+        ),
+    )?;
+    fs::write(src_dir.join("lib.rs"), source)?;
+
+    let output = Command::new("cargo")
+        .args(["test", "--quiet"])
+        .current_dir(&package_dir)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "seed {}:\n{}{}",
+        seed,
+        String::from_utf8_lossy(&output.stderr),
+        String::from_utf8_lossy(&output.stdout),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+
+// Expansions are compared using the snapshots written by the macros, so
+// changes to how items are expanded are visible in the expected files.
+// Setting "ATTR_ALIAS_BLESS=1" writes the current snapshots to them.
+const EXPECTED_FILE: &str = "expected.rs";
+
+const ALIAS_FILE: &str = "attr-aliases.txt";
+
+// Snapshots begin with the locations of invocations, which older compilers
+// cannot provide, and their tokens are printed with the spacing of the
+// compiler. They are therefore only compared for compilers since Rust 1.88.
+fn test(name: &str) -> io::Result<()> {
+    if !common::has_span_file() {
+        return Ok(());
+    }
+
+    let snapshots_dir =
+        Path::new(file!()).with_extension("").canonicalize()?;
+    let case_dir = snapshots_dir.join(name);
+    let tmp_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshots");
+    let package_dir = tmp_dir.join(name);
+    if package_dir.exists() {
+        fs::remove_dir_all(&package_dir)?;
+    }
+
+    let src_dir = package_dir.join("src");
+    fs::create_dir_all(&src_dir)?;
+    fs::write(
+        package_dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.0.0\"\nedition = \
+             \"2021\"\npublish = false\n\n[dependencies]\nattr_alias = {{ \
+             path = '{}' }}\n\n[workspace]\n",
+            name,
+            env!("CARGO_MANIFEST_DIR"),
+        ),
+    )?;
+    let _ = fs::copy(case_dir.join("lib.rs"), src_dir.join("lib.rs"))?;
+    let _ =
+        fs::copy(snapshots_dir.join(ALIAS_FILE), src_dir.join(ALIAS_FILE))?;
+
+    // The directory is only given to packages with build scripts, but it is
+    // read from the environment of the compiler.
+    let out_dir = package_dir.join("out");
+    let output = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .current_dir(&package_dir)
+        .env("ATTR_ALIAS_SNAPSHOT", "1")
+        .env("CARGO_TARGET_DIR", tmp_dir.join("target"))
+        .env("OUT_DIR", &out_dir)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let mut paths: Vec<_> =
+        fs::read_dir(out_dir.join("attr_alias_snapshots"))?
+            .map(|x| x.map(|x| x.path()))
+            .collect::<io::Result<_>>()?;
+    paths.sort();
+    let mut snapshots = String::new();
+    for path in paths {
+        snapshots += &fs::read_to_string(path)?;
+    }
+
+    let expected_file = case_dir.join(EXPECTED_FILE);
+    if env::var_os("ATTR_ALIAS_BLESS").is_some_and(|x| x == "1") {
+        return fs::write(expected_file, snapshots);
+    }
+    assert_eq!(fs::read_to_string(expected_file)?, snapshots);
+
+    Ok(())
+}
+
+#[test]
+fn test_cfg_if() -> io::Result<()> {
+    test("cfg_if")
+}
+
+#[test]
+fn test_deferred() -> io::Result<()> {
+    test("deferred")
+}
+
+#[test]
+fn test_eval_block() -> io::Result<()> {
+    test("eval_block")
+}
+
+#[test]
+fn test_expressions() -> io::Result<()> {
+    test("expressions")
+}
+
+#[test]
+fn test_impl_block() -> io::Result<()> {
+    test("impl_block")
+}
+
+#[test]
+fn test_inner_attributes() -> io::Result<()> {
+    test("inner_attributes")
+}
+
+#[test]
+fn test_nested_modules() -> io::Result<()> {
+    test("nested_modules")
+}
+
+#[test]
+fn test_odd_positions() -> io::Result<()> {
+    test("odd_positions")
+}
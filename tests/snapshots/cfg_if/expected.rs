@@ -0,0 +1,35 @@
+// src/lib.rs:2:1
+#[cfg(target_os = "macos")]
+pub fn platform () -> & 'static str {
+    "macOS"
+}
+#[cfg(all(windows, not(target_os = "macos")))]
+pub fn platform () -> & 'static str {
+    "Windows"
+}
+#[cfg(not(any(target_os = "macos", windows)))]
+#[derive(Clone, Copy, Debug)]
+pub struct Other;
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn platform () -> & 'static str {
+    "other"
+}
+// src/lib.rs:23:1
+#[cfg(debug_assertions)]
+attr_alias :: cfg_if ! {
+    if
+    #[alias(windows_like)]
+    {
+        pub const SEPARATOR : char = '\\';
+    }
+    else {
+        pub const SEPARATOR : char = '/';
+    }
+}
+#[cfg(not(debug_assertions))]
+pub const SEPARATOR : char = '/';
+// src/lib.rs:25:9
+#[cfg(windows)]
+pub const SEPARATOR : char = '\\';
+#[cfg(not(windows))]
+pub const SEPARATOR : char = '/';
@@ -0,0 +1,35 @@
+// Each branch excludes the conditions of the branches before it.
+attr_alias::cfg_if! {
+    if #[alias(macos)] {
+        pub fn platform() -> &'static str {
+            "macOS"
+        }
+    } else if #[cfg(windows)] {
+        pub fn platform() -> &'static str {
+            "Windows"
+        }
+    } else {
+        #[attr_alias(value_type)]
+        pub struct Other;
+
+        pub fn platform() -> &'static str {
+            "other"
+        }
+    }
+}
+
+// Nested invocations are expanded separately, once the outer branch is
+// selected. Snapshots are built using the debug profile.
+attr_alias::cfg_if! {
+    if #[cfg(debug_assertions)] {
+        attr_alias::cfg_if! {
+            if #[alias(windows_like)] {
+                pub const SEPARATOR: char = '\\';
+            } else {
+                pub const SEPARATOR: char = '/';
+            }
+        }
+    } else {
+        pub const SEPARATOR: char = '/';
+    }
+}
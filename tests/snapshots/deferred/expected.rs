@@ -0,0 +1,13 @@
+// src/lib.rs:2:1
+#[cfg_attr(not(docsrs), derive(Clone, Copy, Debug))]
+#[deny(unsafe_code)]
+#[warn(unused_results)]
+pub struct Point {
+    pub x : i32, pub y : i32,
+}
+// src/lib.rs:13:1
+#[repr(C)]
+pub struct Fields {
+    #[cfg_attr(target_os = "macos", doc = "Only documented on macOS.")]
+    pub value : u8,
+}
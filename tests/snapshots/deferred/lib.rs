@@ -0,0 +1,19 @@
+// The derive is expanded before the markers are resolved.
+#[attr_alias::eval(after)]
+#[derive(PartialEq)]
+#[attr_alias(value_type, cfg_attr(not(docsrs), *))]
+#[attr_alias(strict)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+// No outer attribute can be resolved, so resolution is deferred until after
+// each of them.
+#[attr_alias::eval(after)]
+#[derive(Clone)]
+#[repr(C)]
+pub struct Fields {
+    #[attr_alias(macos, cfg_attr(*, doc = "Only documented on macOS."))]
+    pub value: u8,
+}
@@ -0,0 +1,16 @@
+// src/lib.rs:1:1
+#[derive(Clone, Copy, Debug)]
+pub struct Value;
+#[cfg(target_os = "macos")]
+impl Value {
+    #[forbid(unsafe_code)]
+    #[forbid(unused_results)]
+    pub fn macos () {
+    }
+}
+#[cfg(windows)]
+macro_rules ! windows {
+    () => {
+    }
+    ;
+}
@@ -0,0 +1,15 @@
+attr_alias::eval_block! {
+    #[attr_alias(value_type)]
+    pub struct Value;
+
+    #[attr_alias(macos)]
+    impl Value {
+        #[attr_alias(strict, level = forbid)]
+        pub fn macos() {}
+    }
+
+    #[attr_alias(windows_like)]
+    macro_rules! windows {
+        () => {};
+    }
+}
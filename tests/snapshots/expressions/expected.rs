@@ -0,0 +1,35 @@
+// src/lib.rs:2:20
+match () {
+    #[cfg(target_os = "macos")]
+    () => "macOS",
+    #[cfg(not(target_os = "macos"))]
+    () => "other",
+}
+// src/lib.rs:18:18
+Fields {
+    #[cfg(target_os = "macos")]
+    value : 1,
+    #[cfg(not(target_os = "macos"))]
+    value : 2,
+}
+// src/lib.rs:28:5
+#[cfg(target_os = "macos")]
+let value = 1;
+#[cfg(not(target_os = "macos"))]
+let value = 2;
+// src/lib.rs:37:1
+pub fn body () -> u8 {
+    #[cfg(target_os = "macos")]
+    let value = 1;
+    #[cfg(not(target_os = "macos"))]
+    let value = 2;
+    drop (value);
+    #[cfg(target_os = "macos")]
+    {
+        assert_eq ! (1, value);
+    }
+    match value {
+        #[cfg(target_os = "macos")]
+        1 => value, _ => 0,
+    }
+}
@@ -0,0 +1,54 @@
+pub fn platform() -> &'static str {
+    let platform = attr_alias::eval_block! {
+        match () {
+            #[attr_alias(macos)]
+            () => "macOS",
+            #[attr_alias(macos, cfg(not(*)))]
+            () => "other",
+        }
+    };
+    platform
+}
+
+pub fn fields() -> u8 {
+    struct Fields {
+        value: u8,
+    }
+
+    let fields = attr_alias::eval_block!(Fields {
+        #[attr_alias(macos)]
+        value: 1,
+        #[attr_alias(macos, cfg(not(*)))]
+        value: 2,
+    });
+    fields.value
+}
+
+pub fn statements() -> u8 {
+    attr_alias::eval_block! {
+        #[attr_alias(macos)]
+        let value = 1;
+        #[attr_alias(macos, cfg(not(*)))]
+        let value = 2;
+    }
+    value
+}
+
+#[attr_alias::eval]
+pub fn body() -> u8 {
+    #[attr_alias(macos)]
+    let value = 1;
+    #[attr_alias(macos, cfg(not(*)))]
+    let value = 2;
+    #[attr_alias(macos, doc(cfg(*)))]
+    drop(value);
+    #[attr_alias(macos)]
+    {
+        assert_eq!(1, value);
+    }
+    match value {
+        #[attr_alias(macos)]
+        1 => value,
+        _ => 0,
+    }
+}
@@ -0,0 +1,10 @@
+// src/lib.rs:3:1
+impl Type {
+    #[cfg(target_os = "macos")]
+    pub const VALUE : u8 = 1;
+    #[cfg(not(target_os = "macos"))]
+    pub const VALUE : u8 = 2;
+    #[cfg_attr(windows, inline)]
+    pub fn method (&self) {
+    }
+}
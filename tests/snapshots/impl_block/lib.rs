@@ -0,0 +1,13 @@
+pub struct Type;
+
+#[attr_alias::eval]
+impl Type {
+    #[attr_alias(macos)]
+    pub const VALUE: u8 = 1;
+
+    #[attr_alias(macos, cfg(not(*)))]
+    pub const VALUE: u8 = 2;
+
+    #[attr_alias(windows_like, cfg_attr(*, inline))]
+    pub fn method(&self) {}
+}
@@ -0,0 +1,8 @@
+// src/lib.rs:1:1
+pub mod strict {
+    #![deny(unsafe_code)]
+    #![warn(unused_results)]
+    #![cfg_attr(target_os = "macos", doc = "Only on macOS.")]
+    pub fn run () {
+    }
+}
@@ -0,0 +1,7 @@
+#[attr_alias::eval]
+pub mod strict {
+    #![attr_alias(strict)]
+    #![attr_alias(macos, cfg_attr(*, doc = "Only on macOS."))]
+
+    pub fn run() {}
+}
@@ -0,0 +1,13 @@
+// src/lib.rs:1:1
+pub mod outer {
+    #[cfg(target_os = "macos")]
+    pub mod inner {
+        #[derive(Clone, Copy, Debug)]
+        pub struct Value;
+        pub mod innermost {
+            #[cfg(windows)]
+            pub fn windows () {
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+#[attr_alias::eval]
+pub mod outer {
+    #[attr_alias(macos)]
+    pub mod inner {
+        #[attr_alias(value_type)]
+        pub struct Value;
+
+        pub mod innermost {
+            #[attr_alias(windows_like)]
+            pub fn windows() {}
+        }
+    }
+}
@@ -0,0 +1,29 @@
+// src/lib.rs:1:1
+pub struct Fields {
+    #[cfg(target_os = "macos")]
+    pub macos : u8,
+    #[cfg(not(target_os = "macos"))]
+    pub other : u16,
+}
+// src/lib.rs:9:1
+pub enum Variants {
+    #[cfg(windows)]
+    Windows, Other (#[cfg(target_os = "macos")] u8),
+}
+// src/lib.rs:16:1
+pub trait Trait {
+    #[cfg(target_os = "macos")]
+    type Associated;
+    fn method (#[cfg(target_os = "macos")] value : u8);
+}
+// src/lib.rs:24:1
+pub fn statements () -> u8 {
+    #[cfg(target_os = "macos")]
+    let value = 1;
+    #[cfg(not(target_os = "macos"))]
+    let value = 2;
+    match value {
+        #[cfg(windows)]
+        1 => 0, _ => value,
+    }
+}
@@ -0,0 +1,35 @@
+#[attr_alias::eval]
+pub struct Fields {
+    #[attr_alias(macos)]
+    pub macos: u8,
+    #[attr_alias(macos, cfg(not(*)))]
+    pub other: u16,
+}
+
+#[attr_alias::eval]
+pub enum Variants {
+    #[attr_alias(windows_like)]
+    Windows,
+    Other(#[attr_alias(macos)] u8),
+}
+
+#[attr_alias::eval]
+pub trait Trait {
+    #[attr_alias(macos)]
+    type Associated;
+
+    fn method(#[attr_alias(macos)] value: u8);
+}
+
+#[attr_alias::eval]
+pub fn statements() -> u8 {
+    #[attr_alias(macos)]
+    let value = 1;
+    #[attr_alias(macos, cfg(not(*)))]
+    let value = 2;
+    match value {
+        #[attr_alias(windows_like)]
+        1 => 0,
+        _ => value,
+    }
+}
@@ -0,0 +1,254 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+
+// Diagnostics are compared using the lines written for other tools, which
+// do not depend on how the compiler renders them. Setting
+// "ATTR_ALIAS_BLESS=1" writes the current lines to the expected files.
+const EXPECTED_FILE: &str = "expected.jsonl";
+
+// Errors reported by the compiler, instead of by the macros, are only
+// compared for cases with this file, since other lines depend on the version
+// of the compiler. They use the short message format.
+const EXPECTED_COMPILER_FILE: &str = "expected.stderr";
+
+const PREFIX: &str = "attr_alias: ";
+
+// Nightly builds are only tested when the toolchain is installed. They use
+// multiple threads to expand macros, and they are built in a separate
+// directory, since the flags differ. Debugging output uses the same prefix,
+// so it is compared when the variables enable it. The expected lines include
+// the locations of invocations, so older compilers are not tested.
+fn test_with(
+    name: &str,
+    nightly: bool,
+    vars: &[(&str, &str)],
+) -> io::Result<()> {
+    const NIGHTLY: &str = "+nightly";
+
+    if !common::has_span_file() {
+        return Ok(());
+    }
+
+    let case_dir = Path::new(file!())
+        .with_extension("")
+        .join(name)
+        .canonicalize()?;
+    let mut ui_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ui");
+    let mut command = Command::new("cargo");
+    if nightly {
+        if !Command::new("cargo")
+            .args([NIGHTLY, "--version"])
+            .output()
+            .is_ok_and(|x| x.status.success())
+        {
+            return Ok(());
+        }
+        ui_dir = ui_dir.join("nightly");
+        let _ = command.arg(NIGHTLY).env("RUSTFLAGS", "-Zthreads=8");
+    }
+    let package_dir = ui_dir.join(name);
+    if package_dir.exists() {
+        fs::remove_dir_all(&package_dir)?;
+    }
+
+    let src_dir = package_dir.join("src");
+    fs::create_dir_all(&src_dir)?;
+    fs::write(
+        package_dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.0.0\"\nedition = \
+             \"2021\"\npublish = false\n\n[dependencies]\nattr_alias = {{ \
+             path = '{}' }}\n\n[workspace]\n",
+            name,
+            env!("CARGO_MANIFEST_DIR"),
+        ),
+    )?;
+    let _ = fs::copy(case_dir.join("lib.rs"), src_dir.join("lib.rs"))?;
+    let alias_file = case_dir.join("attr-aliases.txt");
+    if alias_file.exists() {
+        let _ = fs::copy(alias_file, src_dir.join("attr-aliases.txt"))?;
+    }
+
+    let output = command
+        .args(["build", "--quiet", "--message-format=short"])
+        .current_dir(&package_dir)
+        .env("ATTR_ALIAS_JSON", "stderr")
+        .env("CARGO_TARGET_DIR", ui_dir.join("target"))
+        .envs(vars.iter().copied())
+        .output()?;
+    // Paths are escaped within the lines.
+    let package_dir = package_dir.to_string_lossy().replace('\\', "\\\\");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: String = stderr
+        .lines()
+        .filter_map(|x| x.strip_prefix(PREFIX))
+        .map(|x| x.replace(&package_dir, "$DIR") + "\n")
+        .collect();
+    let compiler_lines: String = stderr
+        .lines()
+        .filter(|x| {
+            x.starts_with("src/")
+                && (x.contains(": error: ") || x.contains(": error["))
+        })
+        .map(|x| x.to_owned() + "\n")
+        .collect();
+
+    let bless = env::var_os("ATTR_ALIAS_BLESS").is_some_and(|x| x == "1");
+    let expected_file = case_dir.join(EXPECTED_COMPILER_FILE);
+    if expected_file.exists() {
+        if bless {
+            fs::write(&expected_file, &compiler_lines)?;
+        } else {
+            assert_eq!(fs::read_to_string(expected_file)?, compiler_lines);
+        }
+    }
+
+    let expected_file = case_dir.join(EXPECTED_FILE);
+    if bless {
+        return fs::write(expected_file, lines);
+    }
+    assert_eq!(fs::read_to_string(expected_file)?, lines);
+
+    Ok(())
+}
+
+fn test(name: &str) -> io::Result<()> {
+    test_with(name, false, &[])
+}
+
+#[test]
+fn test_allow_cfg() -> io::Result<()> {
+    test("allow_cfg")
+}
+
+#[test]
+fn test_allow_lints() -> io::Result<()> {
+    test("allow_lints")
+}
+
+#[test]
+fn test_cfg_flags() -> io::Result<()> {
+    test("cfg_flags")
+}
+
+#[test]
+fn test_cfg_if() -> io::Result<()> {
+    test("cfg_if")
+}
+
+#[test]
+fn test_compile_error_unless() -> io::Result<()> {
+    test("compile_error_unless")
+}
+
+#[test]
+fn test_conflict() -> io::Result<()> {
+    test("conflict")
+}
+
+#[test]
+fn test_debug() -> io::Result<()> {
+    test_with("debug", false, &[("ATTR_ALIAS_DEBUG", "1")])
+}
+
+#[test]
+fn test_doc_text() -> io::Result<()> {
+    test("doc_text")
+}
+
+#[test]
+fn test_duplicate_alias() -> io::Result<()> {
+    test("duplicate_alias")
+}
+
+#[test]
+fn test_empty_attribute() -> io::Result<()> {
+    test("empty_attribute")
+}
+
+#[test]
+fn test_empty_pattern() -> io::Result<()> {
+    test("empty_pattern")
+}
+
+#[test]
+fn test_feature_aliases() -> io::Result<()> {
+    test("feature_aliases")
+}
+
+// Columns count characters, which differ from bytes in this file.
+#[test]
+fn test_invalid_column() -> io::Result<()> {
+    test("invalid_column")
+}
+
+#[test]
+fn test_invalid_file() -> io::Result<()> {
+    test("invalid_file")
+}
+
+#[test]
+fn test_malformed_marker() -> io::Result<()> {
+    test("malformed_marker")
+}
+
+#[test]
+fn test_missing_file() -> io::Result<()> {
+    test("missing_file")
+}
+
+#[test]
+fn test_msrv_aliases() -> io::Result<()> {
+    test("msrv_aliases")
+}
+
+#[test]
+fn test_negated_bundle() -> io::Result<()> {
+    test("negated_bundle")
+}
+
+// Each invocation reports the same error, which is cached.
+#[test]
+fn test_repeated_error() -> io::Result<()> {
+    test("repeated_error")
+}
+
+#[test]
+fn test_repeated_error_parallel() -> io::Result<()> {
+    test_with("repeated_error", true, &[])
+}
+
+#[test]
+fn test_shadowing() -> io::Result<()> {
+    test("shadowing")
+}
+
+#[test]
+fn test_trigger_attr() -> io::Result<()> {
+    test("trigger_attr")
+}
+
+#[test]
+fn test_trigger_prefix() -> io::Result<()> {
+    test("trigger_prefix")
+}
+
+#[test]
+fn test_unexpected_cfg() -> io::Result<()> {
+    test("unexpected_cfg")
+}
+
+#[test]
+fn test_unknown_alias() -> io::Result<()> {
+    test("unknown_alias")
+}
+
+#[test]
+fn test_unnecessary() -> io::Result<()> {
+    test("unnecessary")
+}
@@ -0,0 +1,8 @@
+#[attr_alias::eval]
+#[attr_alias(macos)]
+#[attr_alias(custom)]
+pub struct Allowed;
+
+#[attr_alias::eval]
+#[attr_alias(unix_like)]
+pub struct Disallowed;
@@ -0,0 +1,2 @@
+// The directive allows all lints, so it does not take a list of them.
+attr_alias::alias_consts!();
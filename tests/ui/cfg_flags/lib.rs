@@ -0,0 +1,5 @@
+// Flags cannot select one of multiple options.
+pub const ANY: &str = attr_alias::cfg_flags!(nightly_docs);
+
+// Names set by the compiler cannot be given as flags.
+pub const WELL_KNOWN: &str = attr_alias::cfg_flags!(unix_docs);
@@ -0,0 +1,7 @@
+attr_alias::cfg_if! {
+    if #[alias(macos)] {
+        pub struct Platform;
+    } else if #[alias(windowz)] {
+        pub struct Platform;
+    }
+}
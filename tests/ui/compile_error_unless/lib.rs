@@ -0,0 +1,3 @@
+attr_alias::compile_error_unless!(always, "this error is not reported");
+
+attr_alias::compile_error_unless!(never, "this crate is never supported");
@@ -0,0 +1,4 @@
+#[attr_alias::eval]
+#[attr_alias(macos)]
+#[attr_alias(!macos)]
+pub struct Never;
@@ -0,0 +1,9 @@
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Macos;
+
+#[attr_alias::eval]
+#[attr_alias(macos, unix_like, cfg_attr(any(*, *), doc = "Apple or Unix."))]
+pub struct Both;
+
+pub const ANY: &str = attr_alias::any!(macos, unix_like);
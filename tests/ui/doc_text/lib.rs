@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(tokio_net)]
+pub struct Described;
@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(strict)]
+pub struct Empty;
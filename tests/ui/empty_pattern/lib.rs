@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(macos, cfg(*);; doc(cfg(*)))]
+pub struct Empty;
@@ -0,0 +1,4 @@
+// The package does not declare any features.
+#[attr_alias::eval]
+#[attr_alias(feat_tokio_net)]
+pub struct Networking;
@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(größe)]
+pub struct Invalid;
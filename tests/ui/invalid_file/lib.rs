@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Invalid;
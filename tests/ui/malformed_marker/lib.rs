@@ -0,0 +1,15 @@
+#[attr_alias::eval]
+#[attr_alias(macos, cfg(all(*, *)))]
+pub struct Wildcards;
+
+#[attr_alias::eval]
+#[attr_alias()]
+pub struct Empty;
+
+#[attr_alias::eval]
+#[attr_alias(macos, level = forbid)]
+pub struct Level;
+
+pub fn unknown_operator() -> bool {
+    attr_alias::any!(macos unix_like)
+}
@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(since_1_80)]
+pub struct Newer;
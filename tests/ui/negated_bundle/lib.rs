@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(!value_type)]
+pub struct Negated;
@@ -0,0 +1,31 @@
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated0;
+
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated1;
+
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated2;
+
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated3;
+
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated4;
+
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated5;
+
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated6;
+
+#[attr_alias::eval]
+#[attr_alias(macos)]
+pub struct Repeated7;
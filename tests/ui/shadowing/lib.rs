@@ -0,0 +1,4 @@
+#[attr_alias::eval]
+#[attr_alias(windows)]
+#[attr_alias(inline)]
+pub fn shadowed() {}
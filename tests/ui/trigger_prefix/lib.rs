@@ -0,0 +1,20 @@
+// Names of triggers must be unique within each module.
+#[attr_alias::eval]
+#[attr_alias(unix_like)]
+pub fn first() {}
+
+#[attr_alias::eval]
+#[attr_alias(unix_like)]
+pub fn second() {}
+
+// Each expansion is invoked from the same location.
+macro_rules! repeated {
+    ( $name:ident ) => {
+        #[attr_alias::eval]
+        #[attr_alias(unix_like)]
+        pub fn $name() {}
+    };
+}
+
+repeated!(third);
+repeated!(fourth);
@@ -0,0 +1,3 @@
+#[attr_alias::eval]
+#[attr_alias(macoss)]
+pub struct Unknown;
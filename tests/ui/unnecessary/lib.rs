@@ -0,0 +1,5 @@
+#[attr_alias::eval]
+pub struct Unnecessary;
+
+#[attr_alias::eval(allow_unused)]
+pub struct Allowed;